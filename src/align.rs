@@ -0,0 +1,283 @@
+// MIT License
+//
+// Copyright (c) 2024 Robin Doer
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+#[cfg(test)]
+mod tests;
+
+use std::cmp;
+use std::rc::Rc;
+
+use crate::options::Options;
+use crate::token::Token;
+use crate::width::WidthModel;
+
+/// The alignment of an item within its column.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Align {
+    /// The item is padded after its comma.
+    Left,
+
+    /// The item is padded in front of it.
+    Right,
+
+    /// Numbers are aligned on their decimal point, which is placed after the
+    /// given number of columns.
+    Decimal(usize),
+}
+
+/// A column of aligned items.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Column {
+    pub width: usize,
+    pub align: Align,
+}
+
+impl Column {
+    /// Creates a column containing `items`.
+    ///
+    /// If decimal alignment is enabled and all items are numbers, they are
+    /// aligned on their decimal point, otherwise they are aligned with
+    /// `align`.
+    fn new<'a, I>(items: I, options: &Options, align: Align) -> Column
+    where
+        I: Iterator<Item = &'a Token> + Clone,
+    {
+        let numbers = || items.clone().all(|t| t.as_data().is_some_and(is_number));
+
+        if options.decimal_alignment() && numbers() {
+            let (mut int, mut frac) = (0, 0);
+
+            for data in items.filter_map(Token::as_data) {
+                let point = decimal_point(data);
+
+                int = cmp::max(int, point);
+                frac = cmp::max(frac, data.len() - point);
+            }
+
+            Column {
+                width: int + frac,
+                align: Align::Decimal(int),
+            }
+        } else {
+            let model = options.width_model();
+            let width = items.map(|t| t.length(model)).max().unwrap_or(0);
+
+            Column { width, align }
+        }
+    }
+
+    /// Returns the number of spaces written in front of and after `token`.
+    pub fn pad(&self, token: &Token, model: WidthModel) -> (usize, usize) {
+        let len = token.length(model);
+        let before = match (self.align, token) {
+            (Align::Left, _) => 0,
+            (Align::Right, _) => self.width.saturating_sub(len),
+            (Align::Decimal(int), Token::Data(data)) => int.saturating_sub(decimal_point(data)),
+            (Align::Decimal(_), _) => 0,
+        };
+
+        (before, self.width.saturating_sub(len + before))
+    }
+}
+
+/// Columns used to align the items of an array or object.
+#[derive(Clone, Debug)]
+pub(crate) struct Cells {
+    pub columns: Rc<[Column]>,
+
+    /// The width of all keys, each followed by the key separator.
+    pub keys: usize,
+
+    /// The width of the item separator between two columns.
+    pub separator: usize,
+
+    /// Whether a new line is started before the first column.
+    pub wrap: bool,
+}
+
+impl Cells {
+    /// Returns the width of a line with one item in each column.
+    ///
+    /// It includes all keys and the separators between the columns.
+    pub fn width(&self) -> usize {
+        let separators = self.separator * (self.columns.len() - 1);
+
+        self.keys + self.columns.iter().map(|c| c.width).sum::<usize>() + separators
+    }
+}
+
+/// Tests whether `data` is a JSON number.
+pub(crate) fn is_number(data: &[u8]) -> bool {
+    data.first()
+        .is_some_and(|c| *c == b'-' || c.is_ascii_digit())
+}
+
+/// Returns the width of the integer part of the number in `data`.
+fn decimal_point(data: &[u8]) -> usize {
+    data.iter()
+        .position(|c| matches!(c, b'.' | b'e' | b'E'))
+        .unwrap_or(data.len())
+}
+
+/// Calculates the column of an expanded array of numbers aligned on their
+/// decimal point.
+///
+/// Returns `None`, if not all `items` are numbers or the aligned numbers do
+/// not fit into `max` when starting at column `indent`. The `max` must leave
+/// room for the comma following a number.
+pub(crate) fn numbers(
+    items: &[Token],
+    options: &Options,
+    indent: usize,
+    max: usize,
+) -> Option<Cells> {
+    let numbers = !items.is_empty() && items.iter().all(|t| t.as_data().is_some_and(is_number));
+
+    if !options.decimal_alignment() || !numbers {
+        return None;
+    }
+
+    let cells = Cells {
+        columns: vec![Column::new(items.iter(), options, Align::Right)].into(),
+        keys: 0,
+        separator: 0,
+        wrap: true,
+    };
+
+    (indent + cells.width() <= max).then_some(cells)
+}
+
+/// Calculates the columns of an array laid out as a grid.
+///
+/// The `items` are written row by row. As many columns as possible are used
+/// without exceeding `max` when starting at column `indent`, which must leave
+/// room for the trailing comma. Returns `None`, if not even two columns fit
+/// into a line.
+pub(crate) fn grid(items: &[Token], options: &Options, indent: usize, max: usize) -> Option<Cells> {
+    // Each item occupies at least one column followed by the separator.
+    let separator = options.separators(false).item.len();
+    let limit = cmp::min(
+        items.len(),
+        (max.saturating_sub(indent) + separator) / (1 + separator),
+    );
+
+    (2..=limit).rev().find_map(|n| {
+        let columns: Vec<Column> = (0..n)
+            .map(|idx| Column::new(items.iter().skip(idx).step_by(n), options, Align::Left))
+            .collect();
+
+        let cells = Cells {
+            columns: columns.into(),
+            keys: 0,
+            separator,
+            wrap: true,
+        };
+
+        (indent + cells.width() <= max).then_some(cells)
+    })
+}
+
+/// Calculates the columns of an array of numeric arrays laid out as a
+/// matrix.
+///
+/// Each of the `rows` must be an array of numbers with the same number of
+/// elements, otherwise `None` is returned.
+pub(crate) fn matrix(rows: &[Token], options: &Options) -> Option<Cells> {
+    let mut items = vec![];
+
+    for row in rows {
+        match row {
+            Token::Array(c) if !c.token.is_empty() => items.push(&c.token),
+            _ => return None,
+        }
+    }
+
+    let len = items.first()?.len();
+    let numbers = |row: &&Vec<Token>| {
+        row.len() == len && row.iter().all(|t| t.as_data().is_some_and(is_number))
+    };
+
+    if !items.iter().all(numbers) {
+        return None;
+    }
+
+    let columns: Vec<Column> = (0..len)
+        .map(|idx| Column::new(items.iter().map(|row| &row[idx]), options, Align::Right))
+        .collect();
+
+    Some(Cells {
+        columns: columns.into(),
+        keys: 0,
+        separator: options.separators(true).item.len(),
+        wrap: false,
+    })
+}
+
+/// Calculates the columns of an array of objects laid out as a table.
+///
+/// Each of the `rows` must be an object with the same keys in the same order
+/// and scalar values, otherwise `None` is returned.
+pub(crate) fn table(rows: &[Token], options: &Options) -> Option<Cells> {
+    let mut items = vec![];
+
+    for row in rows {
+        match row {
+            Token::Object(c) if !c.token.is_empty() => items.push(&c.token),
+            _ => return None,
+        }
+    }
+
+    let keys = *items.first()?;
+    let same = |row: &&Vec<Token>| {
+        row.len() == keys.len()
+            && row
+                .iter()
+                .zip(keys)
+                .enumerate()
+                .all(|(idx, (a, b))| match idx % 2 {
+                    0 => a.as_data() == b.as_data(),
+                    _ => a.as_data().is_some(),
+                })
+    };
+
+    if !items.iter().all(same) {
+        return None;
+    }
+
+    let columns: Vec<Column> = (1..keys.len())
+        .step_by(2)
+        .map(|idx| Column::new(items.iter().map(|row| &row[idx]), options, Align::Left))
+        .collect();
+    let model = options.width_model();
+    let separators = options.separators(true);
+
+    Some(Cells {
+        columns: columns.into(),
+        keys: keys
+            .iter()
+            .step_by(2)
+            .map(|t| t.length(model) + separators.key.len())
+            .sum(),
+        separator: separators.item.len(),
+        wrap: false,
+    })
+}
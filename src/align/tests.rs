@@ -0,0 +1,234 @@
+// MIT License
+//
+// Copyright (c) 2024 Robin Doer
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::align::{grid, is_number, matrix, numbers, table, Align, Cells, Column};
+use crate::options::Options;
+use crate::token::Token;
+use crate::width::WidthModel;
+
+fn data(s: &str) -> Vec<Token> {
+    s.split(' ')
+        .map(|s| Token::Data(s.as_bytes().to_vec()))
+        .collect()
+}
+
+fn widths(cells: Option<Cells>) -> Option<Vec<usize>> {
+    cells.map(|c| c.columns.iter().map(|c| c.width).collect())
+}
+
+fn decimal() -> Options {
+    let mut options = Options::default();
+
+    options.set_decimal_alignment(true);
+
+    options
+}
+
+#[test]
+fn cells_width() {
+    let column = |width| Column {
+        width,
+        align: Align::Left,
+    };
+    let cells = Cells {
+        columns: vec![column(1), column(3)].into(),
+        keys: 0,
+        separator: 2,
+        wrap: false,
+    };
+
+    // "1, 333"
+    assert_eq!(cells.width(), 6);
+}
+
+#[test]
+fn column_pad() {
+    let items = data("1 22 333");
+    let model = WidthModel::Unicode;
+    let left = Column::new(items.iter(), &Options::default(), Align::Left);
+    let right = Column::new(items.iter(), &Options::default(), Align::Right);
+
+    assert_eq!(left.width, 3);
+    assert_eq!(left.pad(&items[0], model), (0, 2));
+    assert_eq!(right.width, 3);
+    assert_eq!(right.pad(&items[0], model), (2, 0));
+}
+
+#[test]
+fn column_decimal() {
+    let items = data("1.5 100 -2.25 3e5");
+    let model = WidthModel::Unicode;
+    let column = Column::new(items.iter(), &decimal(), Align::Left);
+
+    assert_eq!(column.align, Align::Decimal(3));
+    assert_eq!(column.width, 6);
+    assert_eq!(column.pad(&items[0], model), (2, 1));
+    assert_eq!(column.pad(&items[1], model), (0, 3));
+    assert_eq!(column.pad(&items[2], model), (1, 0));
+    assert_eq!(column.pad(&items[3], model), (2, 1));
+}
+
+#[test]
+fn column_decimal_no_numbers() {
+    let items = data("1.5 \"a\"");
+    let column = Column::new(items.iter(), &decimal(), Align::Left);
+
+    assert_eq!(column.align, Align::Left);
+    assert_eq!(column.width, 3);
+}
+
+#[test]
+fn number() {
+    assert!(is_number(b"1"));
+    assert!(is_number(b"-1.5e3"));
+    assert!(!is_number(b"\"1\""));
+    assert!(!is_number(b"true"));
+    assert!(!is_number(b""));
+}
+
+#[test]
+fn numbers_column() {
+    let items = data("1.5 100");
+
+    assert_eq!(widths(numbers(&items, &decimal(), 2, 100)), Some(vec![5]));
+    assert_eq!(widths(numbers(&items, &decimal(), 2, 7)), Some(vec![5]));
+    assert_eq!(widths(numbers(&items, &decimal(), 2, 6)), None);
+    assert_eq!(widths(numbers(&items, &Options::default(), 2, 100)), None);
+    assert_eq!(widths(numbers(&data("1 true"), &decimal(), 2, 100)), None);
+    assert_eq!(widths(numbers(&[], &decimal(), 2, 100)), None);
+}
+
+#[test]
+fn grid_columns() {
+    let items = data("1 22 333 4 5");
+    let options = Options::default();
+
+    assert_eq!(
+        widths(grid(&items, &options, 0, 100)),
+        Some(vec![1, 2, 3, 1, 1])
+    );
+    assert_eq!(
+        widths(grid(&items, &options, 0, 13)),
+        Some(vec![1, 2, 3, 1])
+    );
+    assert_eq!(widths(grid(&items, &options, 0, 12)), Some(vec![1, 2, 3]));
+    assert_eq!(widths(grid(&items, &options, 2, 9)), Some(vec![3, 2]));
+    assert_eq!(widths(grid(&items, &options, 2, 8)), None);
+}
+
+#[test]
+fn grid_separator() {
+    let items = data("1 22 333 4 5");
+    let mut options = Options::default();
+
+    options.set_expanded_separators(",", ":");
+
+    // "1,22,333,4,5"
+    assert_eq!(
+        widths(grid(&items, &options, 0, 12)),
+        Some(vec![1, 2, 3, 1, 1])
+    );
+    assert_eq!(
+        widths(grid(&items, &options, 0, 11)),
+        Some(vec![1, 2, 3, 1])
+    );
+}
+
+#[test]
+fn grid_single() {
+    assert!(grid(&data("1"), &Options::default(), 0, 100).is_none());
+}
+
+#[test]
+fn matrix_columns() {
+    let options = Options::default();
+    let rows = vec![
+        Token::array(1, data("1 20 300"), &options),
+        Token::array(1, data("4000 5 -6"), &options),
+    ];
+
+    assert_eq!(widths(matrix(&rows, &options)), Some(vec![4, 2, 3]));
+}
+
+#[test]
+fn matrix_ragged() {
+    let options = Options::default();
+    let rows = vec![
+        Token::array(1, data("1 2"), &options),
+        Token::array(1, data("3"), &options),
+    ];
+
+    assert!(matrix(&rows, &options).is_none());
+}
+
+#[test]
+fn matrix_no_numbers() {
+    let options = Options::default();
+    let rows = vec![
+        Token::array(1, data("1 2"), &options),
+        Token::array(1, data("3 true"), &options),
+    ];
+
+    assert!(matrix(&rows, &options).is_none());
+    assert!(matrix(&data("1 2"), &options).is_none());
+    assert!(matrix(&[], &options).is_none());
+}
+
+#[test]
+fn table_columns() {
+    let options = Options::default();
+    let rows = vec![
+        Token::object(1, data("\"a\" 1 \"bc\" \"x\""), &options),
+        Token::object(1, data("\"a\" 100 \"bc\" true"), &options),
+    ];
+    let cells = table(&rows, &options).unwrap();
+
+    assert_eq!(widths(Some(cells.clone())), Some(vec![3, 4]));
+    assert_eq!(cells.keys, 11);
+
+    // "a": 100, "bc": true
+    assert_eq!(cells.width(), 20);
+}
+
+#[test]
+fn table_different_keys() {
+    let options = Options::default();
+    let rows = vec![
+        Token::object(1, data("\"a\" 1 \"b\" 2"), &options),
+        Token::object(1, data("\"b\" 1 \"a\" 2"), &options),
+    ];
+
+    assert!(table(&rows, &options).is_none());
+}
+
+#[test]
+fn table_no_scalars() {
+    let options = Options::default();
+    let inner = Token::array(2, data("1"), &options);
+    let rows = vec![
+        Token::object(1, data("\"a\" 1"), &options),
+        Token::object(1, vec![Token::Data(b"\"a\"".to_vec()), inner], &options),
+    ];
+
+    assert!(table(&rows, &options).is_none());
+    assert!(table(&data("1 2"), &options).is_none());
+}
@@ -43,6 +43,11 @@ pub enum Error {
     /// Could not find the object-start token.
     #[error("could not find start of object")]
     NoObjectStart,
+
+    /// Arrays and objects are nested deeper than the configured maximum
+    /// depth.
+    #[error("maximum nesting depth of {0} exceeded")]
+    DepthLimitExceeded(u32),
 }
 
 impl Error {
@@ -56,6 +61,6 @@ impl Error {
 
 impl From<Error> for io::Error {
     fn from(cause: Error) -> io::Error {
-        io::Error::new(io::ErrorKind::Other, cause)
+        io::Error::other(cause)
     }
 }
@@ -22,10 +22,13 @@
 
 use serde_json::ser::{CharEscape, CompactFormatter, Formatter};
 use std::io;
+use std::panic::RefUnwindSafe;
 
 use crate::error::Error;
+use crate::layout::{Fill, IndentStyle, Layout, LayoutPolicy};
 use crate::options::Options;
 use crate::token::Token;
+use crate::width::WidthModel;
 
 fn write_to_vec<F: FnOnce(&mut Vec<u8>) -> io::Result<()>>(f: F) -> io::Result<Vec<u8>> {
     let mut vec = vec![];
@@ -71,6 +74,8 @@ macro_rules! write_func {
 ///   Defaults are:
 ///   - indentation: 2 characters
 ///   - maximum line length: 120 characters
+///   - width measured in Unicode display columns
+///   - no limit of the nesting depth
 ///
 /// * Use [`PrettyCompactFormatter::no_rules()`] without any rules.
 ///
@@ -95,6 +100,225 @@ macro_rules! write_func {
 ///
 /// let formatter = PrettyCompactFormatter::new().with_max_line_length(80);
 /// ```
+///
+/// * Both can be overridden for parts of the document, either by the path or
+///   by the nesting depth of an array or object.
+///
+/// ```
+/// use json_pretty_compact::PrettyCompactFormatter;
+///
+/// let formatter = PrettyCompactFormatter::new()
+///     .with_max_line_length(80)
+///     .with_max_line_length_at("/data", 200)
+///     .with_indent_at_depth(0, 4);
+/// ```
+///
+/// * Change how the width of a line is measured with
+///   [`PrettyCompactFormatter::with_width_model`].
+///
+/// ```
+/// use json_pretty_compact::{PrettyCompactFormatter, WidthModel};
+///
+/// let formatter = PrettyCompactFormatter::new().with_width_model(WidthModel::Bytes);
+/// ```
+///
+/// * Limit the nesting depth of arrays and objects with
+///   [`PrettyCompactFormatter::with_max_depth`].
+///
+/// ```
+/// use json_pretty_compact::PrettyCompactFormatter;
+///
+/// let formatter = PrettyCompactFormatter::new().with_max_depth(128);
+/// ```
+///
+/// * Expand the skeleton of the document and compact its leaves with
+///   [`PrettyCompactFormatter::with_min_compact_depth`] and
+///   [`PrettyCompactFormatter::with_max_expanded_depth`].
+///
+/// ```
+/// use json_pretty_compact::PrettyCompactFormatter;
+///
+/// let formatter = PrettyCompactFormatter::new()
+///     .with_min_compact_depth(1)
+///     .with_max_expanded_depth(2);
+/// ```
+///
+/// * Limit the number of elements of compacted arrays and members of compacted
+///   objects with [`PrettyCompactFormatter::with_max_inline_items`].
+///
+/// ```
+/// use json_pretty_compact::PrettyCompactFormatter;
+///
+/// let formatter = PrettyCompactFormatter::new().with_max_inline_items(10, 3);
+/// ```
+///
+/// * Only compact arrays and objects of scalar values with
+///   [`PrettyCompactFormatter::with_leaf_only_compaction`].
+///
+/// ```
+/// use json_pretty_compact::PrettyCompactFormatter;
+///
+/// let formatter = PrettyCompactFormatter::new().with_leaf_only_compaction(true);
+/// ```
+///
+/// * Pack the elements of long arrays and the members of long objects into
+///   several lines with [`PrettyCompactFormatter::with_array_fill`] and
+///   [`PrettyCompactFormatter::with_object_fill`].
+///
+/// ```
+/// use json_pretty_compact::{Fill, PrettyCompactFormatter};
+///
+/// let formatter = PrettyCompactFormatter::new()
+///     .with_array_fill(Fill::Scalars)
+///     .with_object_fill(Fill::All);
+/// ```
+///
+/// * Lay out long arrays of scalars in aligned columns with
+///   [`PrettyCompactFormatter::with_array_grid`].
+///
+/// ```
+/// use json_pretty_compact::PrettyCompactFormatter;
+///
+/// let formatter = PrettyCompactFormatter::new().with_array_grid(true);
+/// ```
+///
+/// * Align the columns of arrays of numeric arrays with
+///   [`PrettyCompactFormatter::with_matrix`].
+///
+/// ```
+/// use json_pretty_compact::PrettyCompactFormatter;
+///
+/// let formatter = PrettyCompactFormatter::new().with_matrix(true);
+/// ```
+///
+/// * Align the members of arrays of objects with
+///   [`PrettyCompactFormatter::with_table`].
+///
+/// ```
+/// use json_pretty_compact::PrettyCompactFormatter;
+///
+/// let formatter = PrettyCompactFormatter::new().with_table(true);
+/// ```
+///
+/// * Align the values of expanded objects with
+///   [`PrettyCompactFormatter::with_aligned_values`].
+///
+/// ```
+/// use json_pretty_compact::PrettyCompactFormatter;
+///
+/// let formatter = PrettyCompactFormatter::new().with_aligned_values(true);
+/// ```
+///
+/// * Align numbers on their decimal point with
+///   [`PrettyCompactFormatter::with_decimal_alignment`].
+///
+/// ```
+/// use json_pretty_compact::PrettyCompactFormatter;
+///
+/// let formatter = PrettyCompactFormatter::new().with_decimal_alignment(true);
+/// ```
+///
+/// * Put the brackets of a single nested array or object onto the same line
+///   with [`PrettyCompactFormatter::with_hugging`].
+///
+/// ```
+/// use json_pretty_compact::PrettyCompactFormatter;
+///
+/// let formatter = PrettyCompactFormatter::new().with_hugging(true);
+/// ```
+///
+/// * Put closing brackets at the end of the last line of an array or object
+///   with [`PrettyCompactFormatter::with_lisp_closing`].
+///
+/// ```
+/// use json_pretty_compact::PrettyCompactFormatter;
+///
+/// let formatter = PrettyCompactFormatter::new().with_lisp_closing(true);
+/// ```
+///
+/// * Put commas in front of the items with
+///   [`PrettyCompactFormatter::with_leading_commas`].
+///
+/// ```
+/// use json_pretty_compact::PrettyCompactFormatter;
+///
+/// let formatter = PrettyCompactFormatter::new().with_leading_commas(true);
+/// ```
+///
+/// * Put the opening bracket of an expanded member value into its own line
+///   with [`PrettyCompactFormatter::with_allman_brackets`].
+///
+/// ```
+/// use json_pretty_compact::PrettyCompactFormatter;
+///
+/// let formatter = PrettyCompactFormatter::new().with_allman_brackets(true);
+/// ```
+///
+/// * Indent relative to the opening bracket with
+///   [`PrettyCompactFormatter::with_indent_style`].
+///
+/// ```
+/// use json_pretty_compact::{IndentStyle, PrettyCompactFormatter};
+///
+/// let formatter = PrettyCompactFormatter::new().with_indent_style(IndentStyle::Hanging);
+/// ```
+///
+/// * Remove the spaces inside compact and empty brackets with
+///   [`PrettyCompactFormatter::with_bracket_padding`] and
+///   [`PrettyCompactFormatter::with_empty_padding`].
+///
+/// ```
+/// use json_pretty_compact::PrettyCompactFormatter;
+///
+/// let formatter = PrettyCompactFormatter::new()
+///     .with_bracket_padding(false)
+///     .with_empty_padding(false);
+/// ```
+///
+/// * Change the separators between items and between keys and values with
+///   [`PrettyCompactFormatter::with_compact_separators`] and
+///   [`PrettyCompactFormatter::with_expanded_separators`].
+///
+/// ```
+/// use json_pretty_compact::PrettyCompactFormatter;
+///
+/// let formatter = PrettyCompactFormatter::new().with_compact_separators(",", ":");
+/// ```
+///
+/// * Limit the width of a line without its indentation with
+///   [`PrettyCompactFormatter::with_ribbon_width`].
+///
+/// ```
+/// use json_pretty_compact::PrettyCompactFormatter;
+///
+/// let formatter = PrettyCompactFormatter::new().with_ribbon_width(60);
+/// ```
+///
+/// * Install your own rules to decide about compaction with
+///   [`PrettyCompactFormatter::with_policy`].
+///
+/// ```
+/// use json_pretty_compact::{Layout, LayoutContext, PrettyCompactFormatter};
+///
+/// let formatter = PrettyCompactFormatter::new().with_policy(|ctx: &LayoutContext| {
+///     if ctx.depth() == 0 {
+///         Layout::Expanded
+///     } else {
+///         Layout::Default
+///     }
+/// });
+/// ```
+///
+/// * Force the layout of specific paths with
+///   [`PrettyCompactFormatter::with_rule`].
+///
+/// ```
+/// use json_pretty_compact::{Layout, PrettyCompactFormatter};
+///
+/// let formatter = PrettyCompactFormatter::new()
+///     .with_rule("/features/*/geometry/coordinates", Layout::Compact)
+///     .with_rule("/config/routes", Layout::Expanded);
+/// ```
 pub struct PrettyCompactFormatter {
     options: Options,
     token: Vec<Token>,
@@ -125,21 +349,321 @@ impl PrettyCompactFormatter {
         self
     }
 
+    /// Changes the indentation of the children of arrays and objects located
+    /// at `pattern` or nested in them.
+    ///
+    /// The `pattern` is a [JSON pointer], where `*` matches any key or array
+    /// index. If several patterns match, the longest pattern wins. It takes
+    /// precedence over [`PrettyCompactFormatter::with_indent_at_depth`].
+    ///
+    /// [JSON pointer]: https://www.rfc-editor.org/rfc/rfc6901
+    pub fn with_indent_at(mut self, pattern: &str, indent: u32) -> Self {
+        self.options.set_indent_at(pattern, indent);
+        self
+    }
+
+    /// Changes the indentation of the children of arrays and objects at the
+    /// given nesting `depth`.
+    ///
+    /// The top-level array or object has a depth of `0`.
+    pub fn with_indent_at_depth(mut self, depth: u32, indent: u32) -> Self {
+        self.options.set_indent_at_depth(depth, indent);
+        self
+    }
+
+    /// Changes the base column of the indentation.
+    ///
+    /// With [`IndentStyle::Hanging`] the items of an expanded array or object
+    /// are indented relative to the column of the opening bracket instead of
+    /// the start of its line.
+    pub fn with_indent_style(mut self, style: IndentStyle) -> Self {
+        self.options.set_indent_style(style);
+        self
+    }
+
     /// Changes the maximum line length to the given value.
+    ///
+    /// A compacted array or object, including any trailing comma or closing
+    /// brackets on the same line, never exceeds this length.
     pub fn with_max_line_length(mut self, len: u32) -> Self {
         self.options.set_max_len(len);
         self
     }
 
+    /// Changes the maximum line length for arrays and objects located at
+    /// `pattern` or nested in them.
+    ///
+    /// The `pattern` is a [JSON pointer], where `*` matches any key or array
+    /// index. If several patterns match, the longest pattern wins. It takes
+    /// precedence over
+    /// [`PrettyCompactFormatter::with_max_line_length_at_depth`].
+    ///
+    /// [JSON pointer]: https://www.rfc-editor.org/rfc/rfc6901
+    pub fn with_max_line_length_at(mut self, pattern: &str, len: u32) -> Self {
+        self.options.set_max_len_at(pattern, len);
+        self
+    }
+
+    /// Changes the maximum line length for arrays and objects at the given
+    /// nesting `depth`.
+    ///
+    /// The top-level array or object has a depth of `0`.
+    pub fn with_max_line_length_at_depth(mut self, depth: u32, len: u32) -> Self {
+        self.options.set_max_len_at_depth(depth, len);
+        self
+    }
+
+    /// Limits the number of columns following the indentation of a line.
+    ///
+    /// Like the maximum line length, the ribbon width applies to compacted
+    /// arrays and objects, including any trailing comma or closing brackets,
+    /// and to lines filled with several items. It keeps deeply indented lines
+    /// from becoming too dense.
+    pub fn with_ribbon_width(mut self, len: u32) -> Self {
+        self.options.set_ribbon_width(len);
+        self
+    }
+
+    /// Changes the model used to measure the width of a line.
+    pub fn with_width_model(mut self, model: WidthModel) -> Self {
+        self.options.set_width_model(model);
+        self
+    }
+
+    /// Limits the nesting depth of arrays and objects to the given value.
+    ///
+    /// Serialization fails with [`Error::DepthLimitExceeded`] if arrays and
+    /// objects are nested any deeper.
+    pub fn with_max_depth(mut self, depth: u32) -> Self {
+        self.options.set_max_depth(depth);
+        self
+    }
+
+    /// Installs a policy, which decides about the layout of arrays and
+    /// objects.
+    ///
+    /// If the policy returns [`Layout::Default`], the default
+    /// [`WidthPolicy`](crate::WidthPolicy) is applied. The policy must be
+    /// [`RefUnwindSafe`], so that the formatter stays unwind safe.
+    pub fn with_policy<P: LayoutPolicy + Send + Sync + RefUnwindSafe + 'static>(
+        mut self,
+        policy: P,
+    ) -> Self {
+        self.options.set_policy(policy);
+        self
+    }
+
+    /// Never compacts arrays and objects nested less deep than `depth`.
+    ///
+    /// The top-level array or object has a depth of `0`, so a `depth` of `1`
+    /// always puts each member of the top-level container into its own line.
+    pub fn with_min_compact_depth(mut self, depth: u32) -> Self {
+        self.options.set_min_compact_depth(depth);
+        self
+    }
+
+    /// Always compacts arrays and objects nested deeper than `depth`, even if
+    /// they exceed the maximum line length.
+    ///
+    /// The top-level array or object has a depth of `0`.
+    pub fn with_max_expanded_depth(mut self, depth: u32) -> Self {
+        self.options.set_max_expanded_depth(depth);
+        self
+    }
+
+    /// Limits the number of items of a compacted array or object.
+    ///
+    /// An array with more than `array_items` elements or an object with more
+    /// than `object_items` members is expanded, even if it fits into a line.
+    /// Arrays and objects containing such a container are expanded as well.
+    pub fn with_max_inline_items(mut self, array_items: u32, object_items: u32) -> Self {
+        self.options.set_max_inline_items(array_items, object_items);
+        self
+    }
+
+    /// Only compacts arrays and objects, which do not contain any other array
+    /// or object.
+    ///
+    /// Any array or object containing another array or object is expanded.
+    pub fn with_leaf_only_compaction(mut self, leaf_only: bool) -> Self {
+        self.options.set_leaf_only(leaf_only);
+        self
+    }
+
+    /// Packs the elements of expanded arrays into as few lines as possible.
+    ///
+    /// Each line still respects the maximum line length.
+    pub fn with_array_fill(mut self, fill: Fill) -> Self {
+        self.options.set_array_fill(fill);
+        self
+    }
+
+    /// Packs the members of expanded objects into as few lines as possible.
+    ///
+    /// Each line still respects the maximum line length. A member, whose value
+    /// is expanded, always starts in a new line.
+    pub fn with_object_fill(mut self, fill: Fill) -> Self {
+        self.options.set_object_fill(fill);
+        self
+    }
+
+    /// Lays out expanded arrays of scalars in aligned columns.
+    ///
+    /// As many columns as fit into the maximum line length are used, each
+    /// element is padded to the widest element of its column. Takes
+    /// precedence over [`PrettyCompactFormatter::with_array_fill`].
+    pub fn with_array_grid(mut self, grid: bool) -> Self {
+        self.options.set_array_grid(grid);
+        self
+    }
+
+    /// Lays out expanded arrays of numeric arrays as a matrix.
+    ///
+    /// If all elements are arrays of numbers with the same length, each of
+    /// them is compacted into its own line and the numbers are right-aligned
+    /// in columns.
+    pub fn with_matrix(mut self, matrix: bool) -> Self {
+        self.options.set_matrix(matrix);
+        self
+    }
+
+    /// Lays out expanded arrays of objects as a table.
+    ///
+    /// If all elements are objects with the same keys in the same order, each
+    /// of them is compacted into its own line and the values are padded, so
+    /// that keys and values line up in columns.
+    pub fn with_table(mut self, table: bool) -> Self {
+        self.options.set_table(table);
+        self
+    }
+
+    /// Aligns the values of expanded objects.
+    ///
+    /// The keys are padded, so that all values of an object start in the same
+    /// column. Objects packed with [`PrettyCompactFormatter::with_object_fill`]
+    /// are not aligned.
+    pub fn with_aligned_values(mut self, align: bool) -> Self {
+        self.options.set_align_values(align);
+        self
+    }
+
+    /// Aligns numbers on their decimal point.
+    ///
+    /// Applies to expanded arrays of numbers, which are not packed with
+    /// [`PrettyCompactFormatter::with_array_fill`], and to columns of numbers
+    /// in a grid, matrix or table. Integers are right-aligned.
+    pub fn with_decimal_alignment(mut self, align: bool) -> Self {
+        self.options.set_decimal_alignment(align);
+        self
+    }
+
+    /// Hugs the only array or object of an expanded container.
+    ///
+    /// If an expanded array contains a single expanded array or object, or an
+    /// expanded object has a single member with such a value, the brackets
+    /// share their lines, e.g. `[ {` and `} ]`. The hugged container is not
+    /// indented by an extra level.
+    pub fn with_hugging(mut self, hugging: bool) -> Self {
+        self.options.set_hugging(hugging);
+        self
+    }
+
+    /// Appends the closing brackets of expanded arrays and objects to the
+    /// line of their last item.
+    ///
+    /// The closing brackets are taken into account, when deciding whether the
+    /// last item can be compacted.
+    pub fn with_lisp_closing(mut self, lisp: bool) -> Self {
+        self.options.set_lisp_closing(lisp);
+        self
+    }
+
+    /// Writes the commas of expanded arrays and objects in front of the items.
+    ///
+    /// The first item follows the opening bracket, each further item starts
+    /// in a new line with a comma aligned to the opening bracket. The
+    /// configured indentation is not applied to these containers.
+    pub fn with_leading_commas(mut self, leading: bool) -> Self {
+        self.options.set_leading_commas(leading);
+        self
+    }
+
+    /// Opens expanded values of object members in the line after the key.
+    ///
+    /// The opening bracket is indented like the key. Hugged values are not
+    /// affected.
+    pub fn with_allman_brackets(mut self, allman: bool) -> Self {
+        self.options.set_allman_brackets(allman);
+        self
+    }
+
+    /// Pads the items of a compact array or object with a space inside the
+    /// brackets.
+    ///
+    /// Enabled by default, `[ 1, 2 ]`. When disabled, `[1, 2]` is written.
+    pub fn with_bracket_padding(mut self, padding: bool) -> Self {
+        self.options.set_bracket_padding(padding);
+        self
+    }
+
+    /// Writes a space between the brackets of an empty array or object.
+    ///
+    /// Enabled by default, `[ ]`. When disabled, `[]` is written.
+    pub fn with_empty_padding(mut self, padding: bool) -> Self {
+        self.options.set_empty_padding(padding);
+        self
+    }
+
+    /// Changes the separators written in compact arrays and objects.
+    ///
+    /// The `item` separator is written between two items, the `key`
+    /// separator between a key and its value. Defaults to `", "` and `": "`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `item` is not a comma or `key` is not a colon, each
+    /// optionally surrounded by spaces.
+    pub fn with_compact_separators(mut self, item: &str, key: &str) -> Self {
+        self.options.set_compact_separators(item, key);
+        self
+    }
+
+    /// Changes the separators written in expanded arrays and objects.
+    ///
+    /// Trailing spaces of a separator are omitted at the end of a line,
+    /// leading spaces of the item separator at the start of a line. Defaults
+    /// to `", "` and `": "`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `item` is not a comma or `key` is not a colon, each
+    /// optionally surrounded by spaces.
+    pub fn with_expanded_separators(mut self, item: &str, key: &str) -> Self {
+        self.options.set_expanded_separators(item, key);
+        self
+    }
+
+    /// Forces the layout of arrays and objects located at `pattern`.
+    ///
+    /// The `pattern` is a [JSON pointer], where `*` matches any key or array
+    /// index. A rule takes precedence over the depth limits and the installed
+    /// policy. If several rules match, the rule added last wins.
+    ///
+    /// [JSON pointer]: https://www.rfc-editor.org/rfc/rfc6901
+    pub fn with_rule(mut self, pattern: &str, layout: Layout) -> Self {
+        self.options.add_rule(pattern, layout);
+        self
+    }
+
     fn format_json<W: ?Sized + io::Write>(&mut self, writer: &mut W) -> io::Result<()> {
-        if self.token.last().map_or(false, |t| t.is_end_array()) {
+        if self.token.last().is_some_and(|t| t.is_end_array()) {
             self.reduce_array()?;
-        } else if self.token.last().map_or(false, |t| t.is_end_object()) {
+        } else if self.token.last().is_some_and(|t| t.is_end_object()) {
             self.reduce_object()?;
         }
 
         if self.token.len() == 1 {
-            self.token[0].format(writer, &self.options, None)?;
+            self.token[0].format(writer, &self.options, None, 0)?;
         }
 
         Ok(())
@@ -155,7 +679,7 @@ impl PrettyCompactFormatter {
         array.remove(0);
         array.pop();
 
-        self.token.push(Token::Array(level, array));
+        self.token.push(Token::array(level, array, &self.options));
 
         Ok(())
     }
@@ -170,11 +694,18 @@ impl PrettyCompactFormatter {
         object.remove(0);
         object.pop();
 
-        self.token.push(Token::Object(level, object));
+        self.token.push(Token::object(level, object, &self.options));
 
         Ok(())
     }
 
+    fn check_depth(&self) -> Result<(), Error> {
+        match self.options.max_depth() {
+            Some(max) if self.level >= max => Err(Error::DepthLimitExceeded(max)),
+            _ => Ok(()),
+        }
+    }
+
     fn find_last_token<P: FnMut(&Token) -> Option<u32>>(
         &self,
         mut predicate: P,
@@ -256,6 +787,7 @@ impl Formatter for PrettyCompactFormatter {
     write_func!(write_byte_array(&[u8]));
 
     fn begin_array<W: ?Sized + io::Write>(&mut self, _writer: &mut W) -> io::Result<()> {
+        self.check_depth()?;
         self.token.push(Token::BeginArray(self.level));
         self.level += 1;
 
@@ -282,6 +814,7 @@ impl Formatter for PrettyCompactFormatter {
     }
 
     fn begin_object<W: ?Sized + io::Write>(&mut self, _writer: &mut W) -> io::Result<()> {
+        self.check_depth()?;
         self.token.push(Token::BeginObject(self.level));
         self.level += 1;
 
@@ -0,0 +1,352 @@
+// MIT License
+//
+// Copyright (c) 2024 Robin Doer
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+#[cfg(test)]
+mod tests;
+
+use std::borrow::Cow;
+use std::fmt;
+use std::panic::{RefUnwindSafe, UnwindSafe};
+
+use crate::options::Options;
+use crate::token::{Container, Token};
+
+/// The layout of an array or object.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Layout {
+    /// The array or object is put into one line.
+    Compact,
+
+    /// Each element of the array or object is put into its own line.
+    Expanded,
+
+    /// The formatter decides, it falls back to the [`WidthPolicy`].
+    Default,
+}
+
+/// Packing of the items of an expanded array or object.
+///
+/// Instead of putting each item into its own line, as many items as possible
+/// are put into each line without exceeding the maximum line length.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Fill {
+    /// Each item is put into its own line.
+    #[default]
+    Off,
+
+    /// Items are packed, if all array elements or object values are scalars.
+    Scalars,
+
+    /// Items are packed, an expanded array or object always starts in a new
+    /// line.
+    All,
+}
+
+impl Fill {
+    pub(crate) fn applies(&self, token: &Token) -> bool {
+        match (self, token) {
+            (Fill::Off, _) => false,
+            (Fill::Scalars, Token::Array(c)) => c.token.iter().all(|t| Kind::of(t) == Kind::Scalar),
+            (Fill::Scalars, Token::Object(c)) => c
+                .token
+                .iter()
+                .skip(1)
+                .step_by(2)
+                .all(|t| Kind::of(t) == Kind::Scalar),
+            (Fill::All, Token::Array(_) | Token::Object(_)) => true,
+            _ => false,
+        }
+    }
+}
+
+/// The base column for the indentation of expanded arrays and objects.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum IndentStyle {
+    /// Items are indented relative to the line of the opening bracket.
+    #[default]
+    Block,
+
+    /// Items are indented relative to the column of the opening bracket, the
+    /// closing bracket is aligned with the opening one.
+    Hanging,
+}
+
+/// The kind of a JSON value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Kind {
+    /// A string, number, boolean or `null`.
+    Scalar,
+
+    /// An array.
+    Array,
+
+    /// An object.
+    Object,
+}
+
+impl Kind {
+    pub(crate) fn of(token: &Token) -> Kind {
+        match token {
+            Token::Array(_) => Kind::Array,
+            Token::Object(_) => Kind::Object,
+            _ => Kind::Scalar,
+        }
+    }
+}
+
+/// A segment of the path to a JSON value.
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum Segment<'a> {
+    /// The still escaped key of an object member, including its quotes.
+    Key(&'a [u8]),
+
+    /// The index of an array element.
+    Index(usize),
+}
+
+impl<'a> Segment<'a> {
+    /// Returns the segment as it appears in a JSON pointer, but without
+    /// escaping `~` and `/`.
+    pub fn unescaped(&self) -> Cow<'a, str> {
+        match self {
+            Self::Key(key) => match serde_json::from_slice::<String>(key) {
+                Ok(s) => Cow::Owned(s),
+                Err(_) => String::from_utf8_lossy(key),
+            },
+            Self::Index(idx) => Cow::Owned(idx.to_string()),
+        }
+    }
+}
+
+/// Describes an array or object whose layout needs to be decided.
+///
+/// It is passed to [`LayoutPolicy::layout`].
+pub struct LayoutContext<'a> {
+    token: &'a Token,
+    container: &'a Container,
+    path: &'a [Segment<'a>],
+    max_len: Option<u32>,
+    ribbon: Option<u32>,
+    indent: usize,
+    column: usize,
+    suffix: usize,
+}
+
+impl<'a> LayoutContext<'a> {
+    pub(crate) fn new(
+        token: &'a Token,
+        container: &'a Container,
+        path: &'a [Segment<'a>],
+        options: &Options,
+        indent: usize,
+        column: usize,
+        suffix: usize,
+    ) -> LayoutContext<'a> {
+        LayoutContext {
+            token,
+            container,
+            path,
+            max_len: options.max_len_at(path, container.level),
+            ribbon: options.ribbon_width(),
+            indent,
+            column,
+            suffix,
+        }
+    }
+
+    /// Returns whether the container is an array or an object.
+    pub fn kind(&self) -> Kind {
+        Kind::of(self.token)
+    }
+
+    /// Returns the nesting depth of the container.
+    ///
+    /// The top-level container has a depth of `0`.
+    pub fn depth(&self) -> u32 {
+        self.container.level
+    }
+
+    /// Returns the [JSON pointer] to the container.
+    ///
+    /// [JSON pointer]: https://www.rfc-editor.org/rfc/rfc6901
+    pub fn pointer(&self) -> String {
+        self.path.iter().fold(String::new(), |mut acc, segment| {
+            acc.push('/');
+            acc.push_str(&segment.unescaped().replace('~', "~0").replace('/', "~1"));
+            acc
+        })
+    }
+
+    /// Returns the number of elements of an array or members of an object.
+    pub fn len(&self) -> usize {
+        match self.token {
+            Token::Object(_) => self.container.token.len() / 2,
+            _ => self.container.token.len(),
+        }
+    }
+
+    /// Returns `true` if the container has no elements or members.
+    pub fn is_empty(&self) -> bool {
+        self.container.token.is_empty()
+    }
+
+    /// Returns the kinds of the array elements or object values.
+    pub fn children(&self) -> impl Iterator<Item = Kind> + 'a {
+        let skip = match self.token {
+            Token::Object(_) => 1,
+            _ => 0,
+        };
+
+        self.container
+            .token
+            .iter()
+            .skip(skip)
+            .step_by(skip + 1)
+            .map(Kind::of)
+    }
+
+    /// Returns the width of the compacted container.
+    ///
+    /// The width is only measured up to the maximum line length, any wider
+    /// container reports a width above the maximum line length.
+    pub fn width(&self) -> usize {
+        self.container.width
+    }
+
+    /// Returns the indentation of the line, where the container starts.
+    pub fn indent(&self) -> usize {
+        self.indent
+    }
+
+    /// Returns the column where the container starts.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// Returns the width of what follows the container on the same line.
+    pub fn suffix(&self) -> usize {
+        self.suffix
+    }
+
+    /// Returns the maximum line length, which applies to the container.
+    pub fn max_line_length(&self) -> Option<u32> {
+        self.max_len
+    }
+
+    /// Returns the maximum number of columns following the indentation of a
+    /// line.
+    pub fn ribbon_width(&self) -> Option<u32> {
+        self.ribbon
+    }
+
+    /// Returns `true` if the compacted container fits into the current line.
+    ///
+    /// The line must neither exceed the maximum line length nor, without its
+    /// indentation, the ribbon width.
+    pub fn fits(&self) -> bool {
+        let end = self.column + self.width() + self.suffix;
+        let ribbon = match self.ribbon_width() {
+            Some(ribbon) => end.saturating_sub(self.indent) <= ribbon as usize,
+            None => true,
+        };
+
+        ribbon
+            && self
+                .max_line_length()
+                .is_some_and(|max| end <= max as usize)
+    }
+}
+
+/// Decides about the layout of arrays and objects.
+///
+/// The policy is consulted for each array and object. Before an array or
+/// object is compacted, the policy is also consulted for all arrays and
+/// objects nested in it, each placed where it would be written in the line.
+///
+/// ```
+/// use json_pretty_compact::{Kind, Layout, LayoutContext, PrettyCompactFormatter};
+///
+/// // Never compact arrays of objects.
+/// let formatter = PrettyCompactFormatter::new().with_policy(|ctx: &LayoutContext| {
+///     if ctx.kind() == Kind::Array && ctx.children().any(|k| k == Kind::Object) {
+///         Layout::Expanded
+///     } else {
+///         Layout::Default
+///     }
+/// });
+/// ```
+pub trait LayoutPolicy {
+    /// Returns the layout of the array or object described by `ctx`.
+    fn layout(&self, ctx: &LayoutContext) -> Layout;
+}
+
+impl<F: Fn(&LayoutContext) -> Layout> LayoutPolicy for F {
+    fn layout(&self, ctx: &LayoutContext) -> Layout {
+        self(ctx)
+    }
+}
+
+/// The default [`LayoutPolicy`].
+///
+/// A container is compacted if it fits into the current line.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct WidthPolicy;
+
+impl LayoutPolicy for WidthPolicy {
+    fn layout(&self, ctx: &LayoutContext) -> Layout {
+        if ctx.fits() {
+            Layout::Compact
+        } else {
+            Layout::Expanded
+        }
+    }
+}
+
+/// A boxed [`LayoutPolicy`], which is stored in the [`Options`].
+pub(crate) struct Policy(pub Box<dyn LayoutPolicy + Send + Sync + RefUnwindSafe>);
+
+// The policy is only called through shared references, so it cannot be left
+// in a broken state by a panic.
+impl UnwindSafe for Policy {}
+
+impl Policy {
+    /// Returns the layout decided by the policy, [`Layout::Default`] is
+    /// resolved by the [`WidthPolicy`].
+    pub fn layout(&self, ctx: &LayoutContext) -> Layout {
+        match self.0.layout(ctx) {
+            Layout::Default => WidthPolicy.layout(ctx),
+            layout => layout,
+        }
+    }
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        Self(Box::new(WidthPolicy))
+    }
+}
+
+impl fmt::Debug for Policy {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str("Policy")
+    }
+}
@@ -0,0 +1,194 @@
+// MIT License
+//
+// Copyright (c) 2024 Robin Doer
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::layout::{Fill, Kind, Layout, LayoutContext, LayoutPolicy, Segment, WidthPolicy};
+use crate::options::Options;
+use crate::token::Token;
+
+fn data(s: &str) -> Token {
+    Token::Data(s.as_bytes().to_vec())
+}
+
+fn object(options: &Options) -> Token {
+    let inner = Token::array(1, vec![data("1")], options);
+
+    Token::object(
+        0,
+        vec![data("\"a\""), data("1"), data("\"b\""), inner],
+        options,
+    )
+}
+
+macro_rules! ctx {
+    ($token:expr, $path:expr, $options:expr, $column:expr, $suffix:expr) => {
+        ctx!($token, $path, $options, 0, $column, $suffix)
+    };
+    ($token:expr, $path:expr, $options:expr, $indent:expr, $column:expr, $suffix:expr) => {
+        match &$token {
+            Token::Array(c) | Token::Object(c) => {
+                LayoutContext::new(&$token, c, $path, &$options, $indent, $column, $suffix)
+            }
+            _ => unreachable!(),
+        }
+    };
+}
+
+#[test]
+fn segment_key() {
+    assert_eq!(Segment::Key(b"\"a\"").unescaped(), "a");
+}
+
+#[test]
+fn segment_key_escaped() {
+    assert_eq!(Segment::Key(br#""a\"b\u00e4""#).unescaped(), "a\"bä");
+}
+
+#[test]
+fn segment_index() {
+    assert_eq!(Segment::Index(4711).unescaped(), "4711");
+}
+
+#[test]
+fn pointer_root() {
+    let options = Options::default();
+    let token = object(&options);
+    let ctx = ctx!(token, &[], &options, 0, 0);
+
+    assert_eq!(ctx.pointer(), "");
+}
+
+#[test]
+fn pointer_escaped() {
+    let options = Options::default();
+    let token = object(&options);
+    let path = [
+        Segment::Key(b"\"a/b\""),
+        Segment::Index(3),
+        Segment::Key(b"\"m~n\""),
+    ];
+    let ctx = ctx!(token, &path, &options, 0, 0);
+
+    assert_eq!(ctx.pointer(), "/a~1b/3/m~0n");
+}
+
+#[test]
+fn object_context() {
+    let options = Options::default();
+    let token = object(&options);
+    let ctx = ctx!(token, &[], &options, 4, 1);
+
+    assert_eq!(ctx.kind(), Kind::Object);
+    assert_eq!(ctx.depth(), 0);
+    assert_eq!(ctx.len(), 2);
+    assert!(!ctx.is_empty());
+    assert_eq!(
+        ctx.children().collect::<Vec<_>>(),
+        [Kind::Scalar, Kind::Array]
+    );
+    // { "a": 1, "b": [ 1 ] }
+    assert_eq!(ctx.width(), 22);
+    assert_eq!(ctx.column(), 4);
+    assert_eq!(ctx.suffix(), 1);
+}
+
+#[test]
+fn width_policy_fits() {
+    let mut options = Options::default();
+
+    options.set_max_len(27);
+
+    let token = object(&options);
+    let ctx = ctx!(token, &[], &options, 4, 1);
+
+    assert!(ctx.fits());
+    assert_eq!(WidthPolicy.layout(&ctx), Layout::Compact);
+}
+
+#[test]
+fn width_policy_too_wide() {
+    let mut options = Options::default();
+
+    options.set_max_len(26);
+
+    let token = object(&options);
+    let ctx = ctx!(token, &[], &options, 4, 1);
+
+    assert!(!ctx.fits());
+    assert_eq!(WidthPolicy.layout(&ctx), Layout::Expanded);
+}
+
+#[test]
+fn width_policy_ribbon() {
+    let mut options = Options::default();
+
+    options.set_ribbon_width(23);
+
+    // 4 columns of indentation are not counted
+    let token = object(&options);
+    let ctx = ctx!(token, &[], &options, 4, 4, 1);
+
+    assert_eq!(ctx.indent(), 4);
+    assert_eq!(ctx.ribbon_width(), Some(23));
+    assert!(ctx.fits());
+
+    let ctx = ctx!(token, &[], &options, 3, 4, 1);
+
+    assert!(!ctx.fits());
+    assert_eq!(WidthPolicy.layout(&ctx), Layout::Expanded);
+}
+
+#[test]
+fn width_policy_no_rules() {
+    let options = Options::no_rules();
+    let token = object(&options);
+    let ctx = ctx!(token, &[], &options, 0, 0);
+
+    assert!(!ctx.fits());
+    assert_eq!(WidthPolicy.layout(&ctx), Layout::Expanded);
+}
+
+#[test]
+fn fill_applies() {
+    let options = Options::default();
+    let scalars = Token::array(0, vec![data("1"), data("2")], &options);
+    let mixed = Token::array(
+        0,
+        vec![data("1"), Token::array(1, vec![], &options)],
+        &options,
+    );
+
+    assert!(!Fill::Off.applies(&scalars));
+    assert!(Fill::Scalars.applies(&scalars));
+    assert!(!Fill::Scalars.applies(&mixed));
+    assert!(Fill::All.applies(&mixed));
+    assert!(!Fill::All.applies(&data("1")));
+}
+
+#[test]
+fn fill_applies_object() {
+    let options = Options::default();
+    let scalars = Token::object(0, vec![data("\"a\""), data("1")], &options);
+
+    assert!(Fill::Scalars.applies(&scalars));
+    assert!(!Fill::Scalars.applies(&object(&options)));
+    assert!(Fill::All.applies(&object(&options)));
+}
@@ -96,7 +96,8 @@
 //! formatter will change into a pretty format.
 //!
 //! Check the [`PrettyCompactFormatter`] documentation to find out how to
-//! configure the formatter.
+//! configure the formatter. The decision can be replaced with your own
+//! [`LayoutPolicy`].
 //!
 //! ## Usage
 //!
@@ -126,10 +127,18 @@
 //!
 //! [serde_json]: https://docs.rs/serde_json/latest/serde_json/index.html
 
+mod align;
 mod error;
 mod fmt;
+mod layout;
 mod options;
+mod pointer;
 mod token;
+mod width;
 
 pub use crate::error::Error;
 pub use crate::fmt::PrettyCompactFormatter;
+pub use crate::layout::{
+    Fill, IndentStyle, Kind, Layout, LayoutContext, LayoutPolicy, WidthPolicy,
+};
+pub use crate::width::WidthModel;
@@ -23,13 +23,149 @@
 #[cfg(test)]
 mod tests;
 
+use std::panic::RefUnwindSafe;
+
+use crate::layout::{Fill, IndentStyle, Layout, LayoutPolicy, Policy, Segment};
+use crate::pointer::Pattern;
+use crate::token::Token;
+use crate::width::WidthModel;
+
 const DEFAULT_INDENT: u32 = 2;
 const DEFAULT_MAX_LEN: Option<u32> = Some(120);
 
+/// Values, which override an option for some arrays and objects.
+#[derive(Debug)]
+struct Overrides<T> {
+    paths: Vec<(Pattern, T)>,
+    depths: Vec<(u32, T)>,
+}
+
+impl<T: Copy> Overrides<T> {
+    /// Returns the value for the container located at `path` and `depth`.
+    ///
+    /// The longest pattern matching a prefix of `path` wins, then a value for
+    /// exactly `depth`. On a tie the value added last wins.
+    fn get(&self, path: &[Segment], depth: u32) -> Option<T> {
+        self.paths
+            .iter()
+            .filter(|(pattern, _)| pattern.matches_prefix(path))
+            .max_by_key(|(pattern, _)| pattern.len())
+            .map(|(_, value)| *value)
+            .or_else(|| {
+                self.depths
+                    .iter()
+                    .rev()
+                    .find(|(d, _)| *d == depth)
+                    .map(|(_, value)| *value)
+            })
+    }
+
+    fn values(&self) -> impl Iterator<Item = T> + '_ {
+        self.paths
+            .iter()
+            .map(|(_, v)| *v)
+            .chain(self.depths.iter().map(|(_, v)| *v))
+    }
+}
+
+impl<T> Default for Overrides<T> {
+    fn default() -> Self {
+        Self {
+            paths: vec![],
+            depths: vec![],
+        }
+    }
+}
+
+/// The separators written between the items of an array or object.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Separators {
+    /// Written between two items, a comma surrounded by spaces.
+    pub item: String,
+
+    /// Written between a key and its value, a colon surrounded by spaces.
+    pub key: String,
+}
+
+impl Separators {
+    /// Creates new separators.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `item` is not a comma or `key` is not a colon, each
+    /// optionally surrounded by spaces.
+    fn new(item: &str, key: &str) -> Separators {
+        assert_eq!(item.trim_matches(' '), ",", "invalid item separator");
+        assert_eq!(key.trim_matches(' '), ":", "invalid key separator");
+
+        Separators {
+            item: item.to_string(),
+            key: key.to_string(),
+        }
+    }
+
+    /// Returns the item separator without trailing spaces, which is written
+    /// at the end of a line.
+    pub fn comma(&self) -> &str {
+        self.item.trim_end()
+    }
+
+    /// Returns the number of spaces following the comma.
+    pub fn space(&self) -> usize {
+        self.item.len() - self.comma().len()
+    }
+
+    /// Returns the item separator without leading spaces, which is written
+    /// at the start of a line.
+    pub fn leading(&self) -> &str {
+        self.item.trim_start()
+    }
+
+    /// Returns the key separator without trailing spaces, which is written
+    /// at the end of a line.
+    pub fn colon(&self) -> &str {
+        self.key.trim_end()
+    }
+}
+
+impl Default for Separators {
+    fn default() -> Self {
+        Separators::new(", ", ": ")
+    }
+}
+
 #[derive(Debug)]
 pub struct Options {
     indent: u32,
     max_len: Option<u32>,
+    width_model: WidthModel,
+    max_depth: Option<u32>,
+    policy: Policy,
+    rules: Vec<(Pattern, Layout)>,
+    indent_overrides: Overrides<u32>,
+    max_len_overrides: Overrides<u32>,
+    ribbon: Option<u32>,
+    min_compact_depth: Option<u32>,
+    max_expanded_depth: Option<u32>,
+    max_inline_array_items: Option<u32>,
+    max_inline_object_items: Option<u32>,
+    leaf_only: bool,
+    array_fill: Fill,
+    object_fill: Fill,
+    array_grid: bool,
+    matrix: bool,
+    table: bool,
+    align_values: bool,
+    decimal_alignment: bool,
+    hugging: bool,
+    lisp_closing: bool,
+    leading_commas: bool,
+    allman_brackets: bool,
+    indent_style: IndentStyle,
+    bracket_padding: bool,
+    empty_padding: bool,
+    compact_separators: Separators,
+    expanded_separators: Separators,
 }
 
 impl Options {
@@ -40,21 +176,285 @@ impl Options {
         }
     }
 
-    pub fn indent(&self) -> u32 {
-        self.indent
-    }
-
     pub fn set_indent(&mut self, indent: u32) {
         self.indent = indent
     }
 
-    pub fn max_len(&self) -> Option<u32> {
-        self.max_len
+    /// Returns the indentation of the children of the container located at
+    /// `path` and `depth`.
+    pub fn indent_at(&self, path: &[Segment], depth: u32) -> u32 {
+        self.indent_overrides
+            .get(path, depth)
+            .unwrap_or(self.indent)
+    }
+
+    pub fn set_indent_at(&mut self, pattern: &str, indent: u32) {
+        self.indent_overrides
+            .paths
+            .push((Pattern::parse(pattern), indent));
+    }
+
+    pub fn set_indent_at_depth(&mut self, depth: u32, indent: u32) {
+        self.indent_overrides.depths.push((depth, indent));
     }
 
     pub fn set_max_len(&mut self, max_len: u32) {
         self.max_len = Some(max_len);
     }
+
+    /// Returns the maximum line length for the container located at `path`
+    /// and `depth`.
+    pub fn max_len_at(&self, path: &[Segment], depth: u32) -> Option<u32> {
+        self.max_len_overrides.get(path, depth).or(self.max_len)
+    }
+
+    pub fn set_max_len_at(&mut self, pattern: &str, max_len: u32) {
+        self.max_len_overrides
+            .paths
+            .push((Pattern::parse(pattern), max_len));
+    }
+
+    pub fn set_max_len_at_depth(&mut self, depth: u32, max_len: u32) {
+        self.max_len_overrides.depths.push((depth, max_len));
+    }
+
+    pub fn ribbon_width(&self) -> Option<u32> {
+        self.ribbon
+    }
+
+    pub fn set_ribbon_width(&mut self, ribbon: u32) {
+        self.ribbon = Some(ribbon);
+    }
+
+    /// Returns the width at which measuring a compacted container can stop.
+    ///
+    /// Anything at least this wide cannot be compacted anyway.
+    pub fn width_limit(&self) -> usize {
+        self.max_len
+            .into_iter()
+            .chain(self.max_len_overrides.values())
+            .max()
+            .map_or(usize::MAX, |max| max as usize + 1)
+    }
+
+    pub fn width_model(&self) -> WidthModel {
+        self.width_model
+    }
+
+    pub fn set_width_model(&mut self, width_model: WidthModel) {
+        self.width_model = width_model;
+    }
+
+    pub fn max_depth(&self) -> Option<u32> {
+        self.max_depth
+    }
+
+    pub fn set_max_depth(&mut self, max_depth: u32) {
+        self.max_depth = Some(max_depth);
+    }
+
+    pub fn policy(&self) -> &Policy {
+        &self.policy
+    }
+
+    pub fn set_policy<P: LayoutPolicy + Send + Sync + RefUnwindSafe + 'static>(
+        &mut self,
+        policy: P,
+    ) {
+        self.policy = Policy(Box::new(policy));
+    }
+
+    pub fn add_rule(&mut self, pattern: &str, layout: Layout) {
+        self.rules.push((Pattern::parse(pattern), layout));
+    }
+
+    /// Returns the layout of the last rule matching `path`.
+    pub fn rule(&self, path: &[Segment]) -> Option<Layout> {
+        self.rules
+            .iter()
+            .rev()
+            .find(|(pattern, layout)| *layout != Layout::Default && pattern.matches(path))
+            .map(|(_, layout)| *layout)
+    }
+
+    pub fn set_min_compact_depth(&mut self, depth: u32) {
+        self.min_compact_depth = Some(depth);
+    }
+
+    pub fn set_max_expanded_depth(&mut self, depth: u32) {
+        self.max_expanded_depth = Some(depth);
+    }
+
+    pub fn max_inline_array_items(&self) -> Option<u32> {
+        self.max_inline_array_items
+    }
+
+    pub fn max_inline_object_items(&self) -> Option<u32> {
+        self.max_inline_object_items
+    }
+
+    pub fn set_max_inline_items(&mut self, array_items: u32, object_items: u32) {
+        self.max_inline_array_items = Some(array_items);
+        self.max_inline_object_items = Some(object_items);
+    }
+
+    pub fn leaf_only(&self) -> bool {
+        self.leaf_only
+    }
+
+    pub fn set_leaf_only(&mut self, leaf_only: bool) {
+        self.leaf_only = leaf_only;
+    }
+
+    pub fn array_fill(&self) -> Fill {
+        self.array_fill
+    }
+
+    pub fn set_array_fill(&mut self, fill: Fill) {
+        self.array_fill = fill;
+    }
+
+    pub fn object_fill(&self) -> Fill {
+        self.object_fill
+    }
+
+    pub fn set_object_fill(&mut self, fill: Fill) {
+        self.object_fill = fill;
+    }
+
+    pub fn array_grid(&self) -> bool {
+        self.array_grid
+    }
+
+    pub fn set_array_grid(&mut self, grid: bool) {
+        self.array_grid = grid;
+    }
+
+    pub fn matrix(&self) -> bool {
+        self.matrix
+    }
+
+    pub fn set_matrix(&mut self, matrix: bool) {
+        self.matrix = matrix;
+    }
+
+    pub fn table(&self) -> bool {
+        self.table
+    }
+
+    pub fn set_table(&mut self, table: bool) {
+        self.table = table;
+    }
+
+    pub fn align_values(&self) -> bool {
+        self.align_values
+    }
+
+    pub fn set_align_values(&mut self, align: bool) {
+        self.align_values = align;
+    }
+
+    pub fn decimal_alignment(&self) -> bool {
+        self.decimal_alignment
+    }
+
+    pub fn set_decimal_alignment(&mut self, align: bool) {
+        self.decimal_alignment = align;
+    }
+
+    pub fn hugging(&self) -> bool {
+        self.hugging
+    }
+
+    pub fn set_hugging(&mut self, hugging: bool) {
+        self.hugging = hugging;
+    }
+
+    pub fn lisp_closing(&self) -> bool {
+        self.lisp_closing
+    }
+
+    pub fn set_lisp_closing(&mut self, lisp: bool) {
+        self.lisp_closing = lisp;
+    }
+
+    pub fn leading_commas(&self) -> bool {
+        self.leading_commas
+    }
+
+    pub fn set_leading_commas(&mut self, leading: bool) {
+        self.leading_commas = leading;
+    }
+
+    pub fn allman_brackets(&self) -> bool {
+        self.allman_brackets
+    }
+
+    pub fn set_allman_brackets(&mut self, allman: bool) {
+        self.allman_brackets = allman;
+    }
+
+    pub fn indent_style(&self) -> IndentStyle {
+        self.indent_style
+    }
+
+    pub fn set_indent_style(&mut self, style: IndentStyle) {
+        self.indent_style = style;
+    }
+
+    pub fn set_bracket_padding(&mut self, padding: bool) {
+        self.bracket_padding = padding;
+    }
+
+    pub fn set_empty_padding(&mut self, padding: bool) {
+        self.empty_padding = padding;
+    }
+
+    /// Returns the number of spaces between the brackets and the items of
+    /// a compact container, or between the brackets of an empty one.
+    pub fn padding(&self, empty: bool) -> usize {
+        usize::from(if empty {
+            self.empty_padding
+        } else {
+            self.bracket_padding
+        })
+    }
+
+    /// Returns the separators of a compact or an expanded array or object.
+    pub fn separators(&self, compact: bool) -> &Separators {
+        if compact {
+            &self.compact_separators
+        } else {
+            &self.expanded_separators
+        }
+    }
+
+    pub fn set_compact_separators(&mut self, item: &str, key: &str) {
+        self.compact_separators = Separators::new(item, key);
+    }
+
+    pub fn set_expanded_separators(&mut self, item: &str, key: &str) {
+        self.expanded_separators = Separators::new(item, key);
+    }
+
+    /// Returns the layout forced by the nesting `depth`.
+    pub fn depth_layout(&self, depth: u32) -> Option<Layout> {
+        if self.min_compact_depth.is_some_and(|min| depth < min) {
+            Some(Layout::Expanded)
+        } else if self.max_expanded_depth.is_some_and(|max| depth > max) {
+            Some(Layout::Compact)
+        } else {
+            None
+        }
+    }
+
+    /// Tests whether a rule forces an array or object nested in `token` to
+    /// be expanded.
+    pub fn expands_below(&self, path: &[Segment], token: &Token) -> bool {
+        self.rules.iter().any(|(pattern, layout)| {
+            *layout == Layout::Expanded && pattern.matches_below(path, token)
+        })
+    }
 }
 
 impl Default for Options {
@@ -62,6 +462,34 @@ impl Default for Options {
         Self {
             indent: DEFAULT_INDENT,
             max_len: DEFAULT_MAX_LEN,
+            width_model: WidthModel::default(),
+            max_depth: None,
+            policy: Policy::default(),
+            rules: vec![],
+            indent_overrides: Overrides::default(),
+            max_len_overrides: Overrides::default(),
+            ribbon: None,
+            min_compact_depth: None,
+            max_expanded_depth: None,
+            max_inline_array_items: None,
+            max_inline_object_items: None,
+            leaf_only: false,
+            array_fill: Fill::default(),
+            object_fill: Fill::default(),
+            array_grid: false,
+            matrix: false,
+            table: false,
+            align_values: false,
+            decimal_alignment: false,
+            hugging: false,
+            lisp_closing: false,
+            leading_commas: false,
+            allman_brackets: false,
+            indent_style: IndentStyle::default(),
+            bracket_padding: true,
+            empty_padding: true,
+            compact_separators: Separators::default(),
+            expanded_separators: Separators::default(),
         }
     }
 }
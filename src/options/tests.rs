@@ -20,7 +20,9 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
-use crate::options::Options;
+use crate::layout::{Fill, IndentStyle, Layout, Segment};
+use crate::options::{Options, Separators};
+use crate::width::WidthModel;
 
 #[test]
 fn default() {
@@ -28,6 +30,8 @@ fn default() {
 
     assert_eq!(options.indent, 2);
     assert_eq!(options.max_len, Some(120));
+    assert_eq!(options.width_model, WidthModel::Unicode);
+    assert!(options.max_depth.is_none());
 }
 
 #[test]
@@ -36,6 +40,8 @@ fn no_rules() {
 
     assert_eq!(options.indent, 2);
     assert!(options.max_len.is_none());
+    assert_eq!(options.width_model, WidthModel::Unicode);
+    assert!(options.max_depth.is_none());
 }
 
 #[test]
@@ -55,3 +61,361 @@ fn set_max_len() {
 
     assert_eq!(options.max_len, Some(4711));
 }
+
+#[test]
+fn set_width_model() {
+    let mut options = Options::default();
+
+    options.set_width_model(WidthModel::Bytes);
+
+    assert_eq!(options.width_model, WidthModel::Bytes);
+}
+
+#[test]
+fn width_limit() {
+    let mut options = Options::default();
+
+    options.set_max_len(80);
+
+    assert_eq!(options.width_limit(), 81);
+}
+
+#[test]
+fn width_limit_no_rules() {
+    let options = Options::no_rules();
+
+    assert_eq!(options.width_limit(), usize::MAX);
+}
+
+#[test]
+fn set_max_depth() {
+    let mut options = Options::default();
+
+    options.set_max_depth(4711);
+
+    assert_eq!(options.max_depth, Some(4711));
+}
+
+#[test]
+fn rule() {
+    let mut options = Options::default();
+
+    options.add_rule("/a/*", Layout::Compact);
+    options.add_rule("/a/1", Layout::Expanded);
+    options.add_rule("/a/2", Layout::Default);
+
+    let a = Segment::Key(b"\"a\"");
+
+    assert_eq!(options.rule(&[a]), None);
+    assert_eq!(options.rule(&[a, Segment::Index(0)]), Some(Layout::Compact));
+    assert_eq!(
+        options.rule(&[a, Segment::Index(1)]),
+        Some(Layout::Expanded)
+    );
+    assert_eq!(options.rule(&[a, Segment::Index(2)]), Some(Layout::Compact));
+}
+
+#[test]
+fn indent_at() {
+    let mut options = Options::default();
+
+    options.set_indent_at_depth(0, 4);
+    options.set_indent_at_depth(1, 3);
+    options.set_indent_at("/a", 5);
+    options.set_indent_at("/a/*/c", 6);
+
+    let a = Segment::Key(b"\"a\"");
+    let b = Segment::Key(b"\"b\"");
+    let c = Segment::Key(b"\"c\"");
+
+    assert_eq!(options.indent_at(&[], 0), 4);
+    assert_eq!(options.indent_at(&[b], 1), 3);
+    assert_eq!(options.indent_at(&[b, c], 2), 2);
+    assert_eq!(options.indent_at(&[a], 1), 5);
+    assert_eq!(options.indent_at(&[a, b], 2), 5);
+    assert_eq!(options.indent_at(&[a, b, c], 3), 6);
+    assert_eq!(options.indent_at(&[a, b, c, a], 4), 6);
+}
+
+#[test]
+fn max_len_at() {
+    let mut options = Options::no_rules();
+
+    options.set_max_len_at_depth(1, 80);
+    options.set_max_len_at("/a", 200);
+
+    let a = Segment::Key(b"\"a\"");
+    let b = Segment::Key(b"\"b\"");
+
+    assert_eq!(options.max_len_at(&[], 0), None);
+    assert_eq!(options.max_len_at(&[b], 1), Some(80));
+    assert_eq!(options.max_len_at(&[a], 1), Some(200));
+    assert_eq!(options.max_len_at(&[a, b], 2), Some(200));
+}
+
+#[test]
+fn width_limit_overrides() {
+    let mut options = Options::no_rules();
+
+    options.set_max_len_at_depth(1, 80);
+    options.set_max_len_at("/a", 200);
+
+    assert_eq!(options.width_limit(), 201);
+}
+
+#[test]
+fn depth_layout() {
+    let mut options = Options::default();
+
+    assert_eq!(options.depth_layout(0), None);
+
+    options.set_min_compact_depth(2);
+    options.set_max_expanded_depth(3);
+
+    assert_eq!(options.depth_layout(0), Some(Layout::Expanded));
+    assert_eq!(options.depth_layout(1), Some(Layout::Expanded));
+    assert_eq!(options.depth_layout(2), None);
+    assert_eq!(options.depth_layout(3), None);
+    assert_eq!(options.depth_layout(4), Some(Layout::Compact));
+}
+
+#[test]
+fn set_max_inline_items() {
+    let mut options = Options::default();
+
+    assert!(options.max_inline_array_items.is_none());
+    assert!(options.max_inline_object_items.is_none());
+
+    options.set_max_inline_items(4711, 4712);
+
+    assert_eq!(options.max_inline_array_items, Some(4711));
+    assert_eq!(options.max_inline_object_items, Some(4712));
+}
+
+#[test]
+fn set_leaf_only() {
+    let mut options = Options::default();
+
+    assert!(!options.leaf_only);
+
+    options.set_leaf_only(true);
+
+    assert!(options.leaf_only);
+}
+
+#[test]
+fn set_array_fill() {
+    let mut options = Options::default();
+
+    assert_eq!(options.array_fill, Fill::Off);
+
+    options.set_array_fill(Fill::All);
+
+    assert_eq!(options.array_fill, Fill::All);
+}
+
+#[test]
+fn set_object_fill() {
+    let mut options = Options::default();
+
+    assert_eq!(options.object_fill, Fill::Off);
+
+    options.set_object_fill(Fill::Scalars);
+
+    assert_eq!(options.object_fill, Fill::Scalars);
+}
+
+#[test]
+fn set_array_grid() {
+    let mut options = Options::default();
+
+    assert!(!options.array_grid);
+
+    options.set_array_grid(true);
+
+    assert!(options.array_grid);
+}
+
+#[test]
+fn set_matrix() {
+    let mut options = Options::default();
+
+    assert!(!options.matrix);
+
+    options.set_matrix(true);
+
+    assert!(options.matrix);
+}
+
+#[test]
+fn set_table() {
+    let mut options = Options::default();
+
+    assert!(!options.table);
+
+    options.set_table(true);
+
+    assert!(options.table);
+}
+
+#[test]
+fn set_align_values() {
+    let mut options = Options::default();
+
+    assert!(!options.align_values);
+
+    options.set_align_values(true);
+
+    assert!(options.align_values);
+}
+
+#[test]
+fn set_decimal_alignment() {
+    let mut options = Options::default();
+
+    assert!(!options.decimal_alignment);
+
+    options.set_decimal_alignment(true);
+
+    assert!(options.decimal_alignment);
+}
+
+#[test]
+fn set_hugging() {
+    let mut options = Options::default();
+
+    assert!(!options.hugging);
+
+    options.set_hugging(true);
+
+    assert!(options.hugging);
+}
+
+#[test]
+fn set_lisp_closing() {
+    let mut options = Options::default();
+
+    assert!(!options.lisp_closing);
+
+    options.set_lisp_closing(true);
+
+    assert!(options.lisp_closing);
+}
+
+#[test]
+fn set_leading_commas() {
+    let mut options = Options::default();
+
+    assert!(!options.leading_commas);
+
+    options.set_leading_commas(true);
+
+    assert!(options.leading_commas);
+}
+
+#[test]
+fn set_allman_brackets() {
+    let mut options = Options::default();
+
+    assert!(!options.allman_brackets);
+
+    options.set_allman_brackets(true);
+
+    assert!(options.allman_brackets);
+}
+
+#[test]
+fn set_indent_style() {
+    let mut options = Options::default();
+
+    assert_eq!(options.indent_style, IndentStyle::Block);
+
+    options.set_indent_style(IndentStyle::Hanging);
+
+    assert_eq!(options.indent_style, IndentStyle::Hanging);
+}
+
+#[test]
+fn set_bracket_padding() {
+    let mut options = Options::default();
+
+    assert!(options.bracket_padding);
+    assert_eq!(options.padding(false), 1);
+
+    options.set_bracket_padding(false);
+
+    assert!(!options.bracket_padding);
+    assert_eq!(options.padding(false), 0);
+}
+
+#[test]
+fn set_empty_padding() {
+    let mut options = Options::default();
+
+    assert!(options.empty_padding);
+    assert_eq!(options.padding(true), 1);
+
+    options.set_empty_padding(false);
+
+    assert!(!options.empty_padding);
+    assert_eq!(options.padding(true), 0);
+}
+
+#[test]
+fn set_compact_separators() {
+    let mut options = Options::default();
+
+    assert_eq!(options.compact_separators, Separators::new(", ", ": "));
+
+    options.set_compact_separators(",", " : ");
+
+    assert_eq!(options.compact_separators.item, ",");
+    assert_eq!(options.compact_separators.key, " : ");
+    assert_eq!(options.separators(true), &options.compact_separators);
+}
+
+#[test]
+fn set_expanded_separators() {
+    let mut options = Options::default();
+
+    assert_eq!(options.expanded_separators, Separators::new(", ", ": "));
+
+    options.set_expanded_separators(" , ", ":");
+
+    assert_eq!(options.expanded_separators.item, " , ");
+    assert_eq!(options.expanded_separators.key, ":");
+    assert_eq!(options.separators(false), &options.expanded_separators);
+}
+
+#[test]
+#[should_panic(expected = "invalid item separator")]
+fn set_separators_invalid_item() {
+    Options::default().set_compact_separators(";", ":");
+}
+
+#[test]
+#[should_panic(expected = "invalid key separator")]
+fn set_separators_invalid_key() {
+    Options::default().set_expanded_separators(",", " =");
+}
+
+#[test]
+fn separators_trimmed() {
+    let separators = Separators::new(" , ", " : ");
+
+    assert_eq!(separators.comma(), " ,");
+    assert_eq!(separators.space(), 1);
+    assert_eq!(separators.leading(), ", ");
+    assert_eq!(separators.colon(), " :");
+}
+
+#[test]
+fn set_ribbon_width() {
+    let mut options = Options::default();
+
+    assert_eq!(options.ribbon, None);
+
+    options.set_ribbon_width(40);
+
+    assert_eq!(options.ribbon, Some(40));
+}
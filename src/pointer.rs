@@ -0,0 +1,150 @@
+// MIT License
+//
+// Copyright (c) 2024 Robin Doer
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+#[cfg(test)]
+mod tests;
+
+use crate::layout::Segment;
+use crate::token::Token;
+
+/// A segment of a [`Pattern`].
+#[derive(Debug, PartialEq)]
+enum Name {
+    /// Matches any key or index.
+    Any,
+
+    /// Matches the given key. If it is a valid array index, it also matches
+    /// the index.
+    Exact(String, Option<usize>),
+}
+
+impl Name {
+    fn parse(s: &str) -> Name {
+        if s == "*" {
+            return Name::Any;
+        }
+
+        let name = s.replace("~1", "/").replace("~0", "~");
+        let index = if name == "0" || !name.starts_with('0') {
+            name.parse().ok()
+        } else {
+            None
+        };
+
+        Name::Exact(name, index)
+    }
+
+    fn matches(&self, segment: &Segment) -> bool {
+        match (self, segment) {
+            (Name::Any, _) => true,
+            (Name::Exact(_, index), Segment::Index(idx)) => *index == Some(*idx),
+            (Name::Exact(name, _), Segment::Key(key)) => {
+                let raw = key
+                    .strip_prefix(b"\"")
+                    .and_then(|k| k.strip_suffix(b"\""))
+                    .unwrap_or(key);
+
+                if raw.contains(&b'\\') {
+                    segment.unescaped() == name.as_str()
+                } else {
+                    raw == name.as_bytes()
+                }
+            }
+        }
+    }
+}
+
+/// A [JSON pointer], where `*` matches any key or array index.
+///
+/// [JSON pointer]: https://www.rfc-editor.org/rfc/rfc6901
+#[derive(Debug, PartialEq)]
+pub struct Pattern(Vec<Name>);
+
+impl Pattern {
+    /// Parses a pattern, the leading `/` is optional.
+    pub fn parse(s: &str) -> Pattern {
+        let s = s.strip_prefix('/').unwrap_or(s);
+
+        if s.is_empty() {
+            Pattern(vec![])
+        } else {
+            Pattern(s.split('/').map(Name::parse).collect())
+        }
+    }
+
+    /// Returns the number of segments.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Tests whether the pattern matches `path`.
+    pub fn matches(&self, path: &[Segment]) -> bool {
+        self.0.len() == path.len() && self.matches_prefix(path)
+    }
+
+    /// Tests whether `path` starts with the pattern.
+    pub fn matches_prefix(&self, path: &[Segment]) -> bool {
+        self.0.len() <= path.len() && self.0.iter().zip(path).all(|(n, s)| n.matches(s))
+    }
+
+    /// Tests whether the pattern matches an array or object nested in `token`,
+    /// which is located at `path`.
+    pub fn matches_below(&self, path: &[Segment], token: &Token) -> bool {
+        if self.0.len() <= path.len() || !self.0.iter().zip(path).all(|(n, s)| n.matches(s)) {
+            return false;
+        }
+
+        let mut stack = vec![(token, path.len())];
+
+        while let Some((token, depth)) = stack.pop() {
+            let (object, container) = match token {
+                Token::Array(c) => (false, c),
+                Token::Object(c) => (true, c),
+                _ => continue,
+            };
+
+            if depth == self.0.len() {
+                return true;
+            }
+
+            let name = &self.0[depth];
+
+            if object {
+                for pair in container.token.chunks_exact(2) {
+                    if let Token::Data(key) = &pair[0] {
+                        if name.matches(&Segment::Key(key)) {
+                            stack.push((&pair[1], depth + 1));
+                        }
+                    }
+                }
+            } else {
+                for (idx, t) in container.token.iter().enumerate() {
+                    if name.matches(&Segment::Index(idx)) {
+                        stack.push((t, depth + 1));
+                    }
+                }
+            }
+        }
+
+        false
+    }
+}
@@ -0,0 +1,120 @@
+// MIT License
+//
+// Copyright (c) 2024 Robin Doer
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::layout::Segment;
+use crate::options::Options;
+use crate::pointer::{Name, Pattern};
+use crate::token::Token;
+
+fn data(s: &str) -> Token {
+    Token::Data(s.as_bytes().to_vec())
+}
+
+#[test]
+fn parse_root() {
+    assert_eq!(Pattern::parse(""), Pattern(vec![]));
+    assert_eq!(Pattern::parse("/"), Pattern(vec![]));
+}
+
+#[test]
+fn parse() {
+    assert_eq!(
+        Pattern::parse("/a/*/0/01/m~0n~1o"),
+        Pattern(vec![
+            Name::Exact("a".to_string(), None),
+            Name::Any,
+            Name::Exact("0".to_string(), Some(0)),
+            Name::Exact("01".to_string(), None),
+            Name::Exact("m~n/o".to_string(), None),
+        ])
+    );
+}
+
+#[test]
+fn parse_without_slash() {
+    assert_eq!(Pattern::parse("a/b"), Pattern::parse("/a/b"));
+}
+
+#[test]
+fn matches() {
+    let pattern = Pattern::parse("/a/*/b");
+
+    assert!(pattern.matches(&[
+        Segment::Key(b"\"a\""),
+        Segment::Index(7),
+        Segment::Key(b"\"b\""),
+    ]));
+    assert!(pattern.matches(&[
+        Segment::Key(b"\"a\""),
+        Segment::Key(b"\"x\""),
+        Segment::Key(b"\"b\""),
+    ]));
+    assert!(!pattern.matches(&[Segment::Key(b"\"a\""), Segment::Index(7)]));
+    assert!(!pattern.matches(&[
+        Segment::Key(b"\"a\""),
+        Segment::Index(7),
+        Segment::Key(b"\"c\""),
+    ]));
+}
+
+#[test]
+fn matches_index() {
+    let pattern = Pattern::parse("/1");
+
+    assert!(pattern.matches(&[Segment::Index(1)]));
+    assert!(pattern.matches(&[Segment::Key(b"\"1\"")]));
+    assert!(!pattern.matches(&[Segment::Index(2)]));
+}
+
+#[test]
+fn matches_escaped_key() {
+    let pattern = Pattern::parse("/a\"b~1c");
+
+    assert!(pattern.matches(&[Segment::Key(br#""a\"b/c""#)]));
+    assert!(!pattern.matches(&[Segment::Key(br#""a\"b/d""#)]));
+}
+
+#[test]
+fn matches_prefix() {
+    let pattern = Pattern::parse("/a");
+
+    assert!(pattern.matches_prefix(&[Segment::Key(b"\"a\"")]));
+    assert!(pattern.matches_prefix(&[Segment::Key(b"\"a\""), Segment::Index(0)]));
+    assert!(!pattern.matches_prefix(&[]));
+    assert!(!pattern.matches_prefix(&[Segment::Key(b"\"b\""), Segment::Index(0)]));
+}
+
+#[test]
+fn matches_below() {
+    let options = Options::default();
+    let inner = Token::array(2, vec![data("1")], &options);
+    let obj = Token::object(1, vec![data("\"b\""), inner], &options);
+    let token = Token::array(0, vec![data("1"), obj], &options);
+
+    assert!(Pattern::parse("/*/b").matches_below(&[], &token));
+    assert!(Pattern::parse("/1/b").matches_below(&[], &token));
+    assert!(Pattern::parse("/1").matches_below(&[], &token));
+    assert!(!Pattern::parse("/0").matches_below(&[], &token));
+    assert!(!Pattern::parse("/1/c").matches_below(&[], &token));
+    assert!(!Pattern::parse("/*/b/0").matches_below(&[], &token));
+    assert!(!Pattern::parse("").matches_below(&[], &token));
+}
@@ -23,11 +23,16 @@
 #[cfg(test)]
 mod tests;
 
+use std::cmp;
 use std::fmt::{self, Display, Formatter};
 use std::io;
+use std::mem;
 
+use crate::align::{self, Cells};
 use crate::error::Error;
+use crate::layout::{IndentStyle, Kind, Layout, LayoutContext, Segment};
 use crate::options::Options;
+use crate::width::WidthModel;
 
 macro_rules! write_indent {
     ($writer:expr, $len:ident) => {
@@ -37,6 +42,25 @@ macro_rules! write_indent {
     };
 }
 
+/// A reduced array or object.
+#[derive(Debug)]
+pub struct Container {
+    /// The nesting level.
+    pub level: u32,
+
+    /// The width of the compacted container, capped at
+    /// [`Options::width_limit()`].
+    pub width: usize,
+
+    /// Whether the container and all nested containers are allowed to be
+    /// compacted.
+    pub inline: bool,
+
+    /// The elements of an array or the alternating keys and values of an
+    /// object.
+    pub token: Vec<Token>,
+}
+
 #[derive(Debug)]
 pub enum Token {
     BeginObject(u32),
@@ -44,11 +68,98 @@ pub enum Token {
     BeginArray(u32),
     EndArray,
     Data(Vec<u8>),
-    Array(u32, Vec<Token>),
-    Object(u32, Vec<Token>),
+    Array(Container),
+    Object(Container),
 }
 
 impl Token {
+    /// Creates an array token from its elements.
+    ///
+    /// The width of the compacted array is calculated once here.
+    pub fn array(level: u32, token: Vec<Token>, options: &Options) -> Token {
+        let width = Self::compact_width(&token, false, options);
+        let inline = Self::can_inline(
+            &token,
+            token.len(),
+            options.max_inline_array_items(),
+            options,
+        );
+
+        Token::Array(Container {
+            level,
+            width,
+            inline,
+            token,
+        })
+    }
+
+    /// Creates an object token from its alternating keys and values.
+    ///
+    /// The width of the compacted object is calculated once here.
+    pub fn object(level: u32, token: Vec<Token>, options: &Options) -> Token {
+        let width = Self::compact_width(&token, true, options);
+        let inline = Self::can_inline(
+            &token,
+            token.len() / 2,
+            options.max_inline_object_items(),
+            options,
+        );
+
+        Token::Object(Container {
+            level,
+            width,
+            inline,
+            token,
+        })
+    }
+
+    fn compact_width(token: &[Token], object: bool, options: &Options) -> usize {
+        if token.is_empty() {
+            return 2 + options.padding(true); // [ ] or { }
+        }
+
+        let limit = options.width_limit();
+        let separators = options.separators(true);
+        let mut width = 2 + 2 * options.padding(false); // [ ] or { }
+
+        // Each element of an array is followed by the item separator, except
+        // the last one. In an object each key is followed by the key separator
+        // and each value, except the last one, by the item separator.
+        for (idx, t) in token.iter().enumerate() {
+            let separator = if object && idx % 2 == 0 {
+                separators.key.len()
+            } else if idx + 1 < token.len() {
+                separators.item.len()
+            } else {
+                0
+            };
+
+            width += t.length(options.width_model()) + separator;
+
+            if width >= limit {
+                return limit;
+            }
+        }
+
+        width
+    }
+
+    fn can_inline(
+        token: &[Token],
+        items: usize,
+        max_items: Option<u32>,
+        options: &Options,
+    ) -> bool {
+        if max_items.is_some_and(|max| items > max as usize) {
+            return false;
+        }
+
+        token.iter().all(|t| match t {
+            Token::Array(c) | Token::Object(c) => c.inline && !options.leaf_only(),
+            _ => true,
+        })
+    }
+
     pub fn as_begin_object(&self) -> Option<u32> {
         match self {
             Self::BeginObject(level) => Some(*level),
@@ -97,172 +208,616 @@ impl Token {
             .ok_or_else(|| Error::unexpected_event("Data", di))
     }
 
-    pub fn length(&self) -> usize {
+    /// Returns the width of the compacted token measured with `model`.
+    ///
+    /// The width of arrays and objects was already calculated when the token
+    /// was created.
+    pub fn length(&self, model: WidthModel) -> usize {
         match self {
             Token::BeginObject(_) | Token::EndObject | Token::BeginArray(_) | Token::EndArray => 0,
-            Token::Data(vec) => vec.len(),
-            Token::Array(_, token) => {
-                let n = token.iter().fold(0, |acc, t| acc + t.length());
-
-                // add all commas between elements
-                let inner = n + (token.len().saturating_sub(1) * 2);
-
-                if inner > 0 {
-                    4 + inner // plus surrounding [ ]
-                } else {
-                    3 // [ ]
-                }
-            }
-            Token::Object(_, token) => {
-                let n = token.iter().fold(0, |acc, t| acc + t.length());
-                let num_keys = token.len() / 2;
-
-                // add ": " between key & value and commas between elements
-                let inner = n + 2 * num_keys + (num_keys.saturating_sub(1) * 2);
-
-                if inner > 0 {
-                    4 + inner // plus surrounding {}
-                } else {
-                    3 // [ ] or { }
-                }
-            }
+            Token::Data(vec) => model.measure(vec),
+            Token::Array(c) | Token::Object(c) => c.width,
         }
     }
 
+    /// Writes the token into `writer`.
+    ///
+    /// If `compact` is set, the layout of the token is forced, otherwise it is
+    /// calculated from the configured `options`. `suffix` is the number of
+    /// characters, which follows the token on the same line.
+    ///
+    /// Nested arrays and objects are written without recursion, the stack of
+    /// currently open containers is managed explicitly.
     pub fn format<W: ?Sized + io::Write>(
         &self,
         writer: &mut W,
         options: &Options,
-        forced_compact: Option<bool>,
+        compact: Option<bool>,
+        suffix: usize,
     ) -> io::Result<()> {
-        match self {
-            Token::BeginObject(_) | Token::EndObject | Token::BeginArray(_) | Token::EndArray => {}
-            Token::Data(vec) => writer.write_all(vec)?,
-            Token::Array(level, token) => {
-                let compact = forced_compact.unwrap_or_else(|| self.can_compact(options, None));
-                let mut first = true;
+        let mut stack = vec![];
+        let mut path = vec![];
+        let place = Place {
+            indent: 0,
+            column: 0,
+            suffix,
+        };
 
-                let spaces = (level * options.indent()) as usize;
-                let spaces_next = ((level + 1) * options.indent()) as usize;
+        self.open(writer, options, compact, &mut path, place, &mut stack)?;
 
-                if compact {
-                    writer.write_all(b"[ ")?;
-                } else {
-                    writer.write_all(b"[\n")?;
+        while let Some(frame) = stack.last_mut() {
+            let container = frame.container;
+
+            if frame.next >= container.token.len() {
+                frame.close(writer)?;
+                stack.pop();
+
+                // Each container, except the top-level one, has a path segment.
+                if !stack.is_empty() {
+                    path.pop();
                 }
 
-                for t in token {
-                    if !first {
-                        if compact {
-                            writer.write_all(b", ")?;
-                        } else {
-                            writer.write_all(b",\n")?;
-                        }
-                    }
+                continue;
+            }
+
+            let first = frame.next == 0;
+            let model = options.width_model();
+            let separators = options.separators(frame.compact);
+            let index = if frame.object {
+                frame.next / 2
+            } else {
+                frame.next
+            };
+
+            let (key, segment) = if frame.object {
+                let key = container.token[frame.next].as_data_err()?;
+
+                frame.next += 1;
 
-                    if !compact {
-                        write_indent!(writer, spaces_next);
+                (Some(key), Segment::Key(key))
+            } else {
+                (None, Segment::Index(frame.next))
+            };
+
+            let t = &container.token[frame.next];
+
+            frame.next += 1;
+
+            // followed by a comma, if it is not the last element, otherwise by
+            // the closing brackets on the same line
+            let suffix = if frame.next < container.token.len() {
+                if frame.leading {
+                    0
+                } else {
+                    separators.comma().len()
+                }
+            } else {
+                frame.tail.unwrap_or(0)
+            };
+            // an aligned key is padded up to the widest key of the object
+            let (key_width, key_pad) = match key {
+                Some(key) => {
+                    let width = model.measure(key);
+                    let aligned = frame.keys.unwrap_or(width);
+
+                    (aligned + separators.key.len(), aligned - width)
+                }
+                None => (0, 0),
+            };
+            let mut place = match frame.hug {
+                // a hugged item shares the lines of the opening and closing
+                // brackets
+                Some(column) => Place {
+                    indent: frame.line,
+                    column: column + key_width,
+                    suffix,
+                },
+                None => Place {
+                    indent: frame.child_indent,
+                    column: frame.child_indent + key_width,
+                    suffix,
+                },
+            };
+
+            path.push(segment);
+
+            // In fill mode the item is appended to the current line, if it
+            // fits there compacted.
+            let packed = match (first, frame.column, frame.max_len) {
+                (false, Some(column), Some(max)) => {
+                    let column = column + separators.item.len() + key_width;
+                    let end = column + t.length(model) + suffix;
+                    // the ribbon is measured from the indentation of the line
+                    let ribbon = match options.ribbon_width() {
+                        Some(ribbon) => end - frame.line <= ribbon as usize,
+                        None => true,
+                    };
+                    let packed = Place {
+                        indent: frame.line,
+                        column,
+                        ..place
+                    };
+
+                    if end <= max && ribbon && t.can_compact(options, &mut path, packed) {
+                        place = packed;
+                        true
+                    } else {
+                        false
                     }
+                }
+                _ => false,
+            };
+
+            // Decide whether the item starts in a new line, otherwise it is
+            // padded by the given number of spaces.
+            let mut pad = 0;
+            let mut lead = 0;
+            let mut newline = !frame.compact && !packed && frame.hug.is_none();
+
+            if let Some(cells) = &frame.cells {
+                let column = index % cells.columns.len();
+                let (before, after) = cells.columns[column].pad(t, model);
+
+                newline = cells.wrap && column == 0;
+                lead = before;
+                place.column += before;
+                pad = mem::replace(&mut frame.pad, after);
+            }
 
-                    t.format(writer, options, None)?;
+            // the first item follows the opening bracket
+            if frame.leading && first {
+                newline = false;
+            }
 
-                    first = false;
+            // a leading comma is written after the line break
+            if !first && (!newline || !frame.leading) {
+                writer.write_all(separators.comma().as_bytes())?;
+            }
+
+            if newline {
+                if !first {
+                    writer.write_all(b"\n")?;
                 }
 
-                if compact && first {
-                    writer.write_all(b"]")?;
-                } else if compact && !first {
-                    writer.write_all(b" ]")?;
+                if frame.leading {
+                    let indent = frame.indent;
+
+                    write_indent!(writer, indent);
+                    writer.write_all(separators.leading().as_bytes())?;
+                    frame.line = indent;
                 } else {
-                    writer.write_all(b"\n")?;
-                    write_indent!(writer, spaces);
-                    writer.write_all(b"]")?;
+                    let indent = place.indent;
+
+                    write_indent!(writer, indent);
+                    frame.line = indent;
                 }
+            } else if !first {
+                let spaces = pad + separators.space();
+
+                write_indent!(writer, spaces);
             }
-            Token::Object(level, token) => {
-                let compact = forced_compact.unwrap_or_else(|| self.can_compact(options, None));
-                let mut first = true;
 
-                let spaces = (level * options.indent()) as usize;
-                let spaces_next = ((level + 1) * options.indent()) as usize;
-                let mut cur_indent = 0;
+            // with leading commas the item shares the line of its comma
+            if frame.leading && frame.hug.is_none() {
+                place.indent = frame.line;
+            }
 
-                if compact {
-                    writer.write_all(b"{ ")?;
-                    cur_indent += 2;
+            let compact = frame.hug.is_none()
+                && (frame.compact
+                    || packed
+                    || frame.rows.is_some()
+                    || t.can_compact(options, &mut path, place));
+
+            // an expanded value starts in the line after its key
+            let allman = key.is_some()
+                && !compact
+                && frame.hug.is_none()
+                && Kind::of(t) != Kind::Scalar
+                && options.allman_brackets();
+
+            if let Some(key) = key {
+                writer.write_all(key)?;
+                let separator = if allman {
+                    separators.colon()
                 } else {
-                    writer.write_all(b"{\n")?;
-                    cur_indent = spaces;
+                    &separators.key
+                };
+
+                writer.write_all(separator.as_bytes())?;
+            }
+
+            if allman {
+                let indent = frame.child_indent;
+
+                writer.write_all(b"\n")?;
+                write_indent!(writer, indent);
+                place.indent = indent;
+                place.column = indent;
+            } else {
+                let spaces = key_pad + lead;
+
+                write_indent!(writer, spaces);
+            }
+
+            // Only a compacted item can be followed by another item on the same
+            // line.
+            frame.column = if frame.fill && compact {
+                Some(place.column + t.length(model))
+            } else {
+                None
+            };
+
+            let rows = frame.rows.clone();
+
+            if t.open(writer, options, Some(compact), &mut path, place, &mut stack)? {
+                // the items of the rows are aligned to each other
+                if let (Some(rows), Some(frame)) = (rows, stack.last_mut()) {
+                    frame.cells = Some(rows);
                 }
+            } else {
+                path.pop();
+            }
+        }
 
-                let iter = token.chunks_exact(2).map(|chunk| (&chunk[0], &chunk[1]));
+        Ok(())
+    }
 
-                for (t1, t2) in iter {
-                    let key = t1.as_data_err()?;
+    /// Tests whether the only item of an expanded array or object can be
+    /// hugged by its brackets.
+    ///
+    /// This is the case, if the item is an array or object, which is expanded
+    /// itself.
+    fn hug<'a>(
+        &'a self,
+        options: &Options,
+        path: &mut Vec<Segment<'a>>,
+        child_indent: usize,
+    ) -> bool {
+        let (segment, key_width, t) = match self {
+            Token::Array(c) => match c.token.as_slice() {
+                [t] => (Segment::Index(0), 0, t),
+                _ => return false,
+            },
+            Token::Object(c) => match c.token.as_slice() {
+                [Token::Data(key), t] => {
+                    let key_width =
+                        options.width_model().measure(key) + options.separators(false).key.len();
+
+                    (Segment::Key(key), key_width, t)
+                }
+                _ => return false,
+            },
+            _ => return false,
+        };
 
-                    if !first {
-                        if compact {
-                            writer.write_all(b", ")?;
-                            cur_indent += 2;
-                        } else {
-                            writer.write_all(b",\n")?;
-                            cur_indent = 0;
-                        }
-                    }
+        if Kind::of(t) == Kind::Scalar {
+            return false;
+        }
 
-                    if !compact {
-                        write_indent!(writer, spaces_next);
-                        cur_indent += spaces_next;
-                    }
+        let place = Place {
+            indent: child_indent,
+            column: child_indent + key_width,
+            suffix: 0,
+        };
 
-                    writer.write_all(key)?;
-                    writer.write_all(b": ")?;
-                    cur_indent += key.len() + 2;
+        path.push(segment);
 
-                    // Let's check if the value can be put compacted behind the key in one line.
-                    let forced_compact = t2.can_compact(options, Some(cur_indent));
+        let compact = t.can_compact(options, path, place);
 
-                    if !forced_compact {
-                        // There is not enough space to put the value into the same line.
-                        t2.format(writer, options, Some(false))?;
-                    } else {
-                        t2.format(writer, options, None)?;
+        path.pop();
+
+        !compact
+    }
+
+    /// Tests whether each row of a matrix or table can be compacted at
+    /// `place`, when it is padded to the width of the aligned `rows`.
+    fn compacts_rows<'a>(
+        &'a self,
+        rows: &Cells,
+        options: &Options,
+        path: &mut Vec<Segment<'a>>,
+        place: Place,
+    ) -> bool {
+        let container = match self {
+            Token::Array(c) => c,
+            _ => return false,
+        };
+        let model = options.width_model();
+        let width = rows.width() + 2 + 2 * options.padding(false);
+
+        container.token.iter().enumerate().all(|(idx, t)| {
+            // the padding follows the row like a suffix
+            let suffix = place.suffix + width.saturating_sub(t.length(model));
+
+            path.push(Segment::Index(idx));
+
+            let compact = t.can_compact(options, path, Place { suffix, ..place });
+
+            path.pop();
+
+            compact
+        })
+    }
+
+    /// Starts writing the token located at `path`.
+    ///
+    /// Data is written immediately. For arrays and objects only the opening
+    /// bracket is written and a new [`Frame`] is pushed onto the `stack`, in
+    /// this case `true` is returned.
+    fn open<'a, W: ?Sized + io::Write>(
+        &'a self,
+        writer: &mut W,
+        options: &Options,
+        compact: Option<bool>,
+        path: &mut Vec<Segment<'a>>,
+        place: Place,
+        stack: &mut Vec<Frame<'a>>,
+    ) -> io::Result<bool> {
+        match self {
+            Token::BeginObject(_) | Token::EndObject | Token::BeginArray(_) | Token::EndArray => {
+                Ok(false)
+            }
+            Token::Data(vec) => writer.write_all(vec).map(|()| false),
+            Token::Array(container) | Token::Object(container) => {
+                let object = matches!(self, Token::Object(_));
+                let compact = compact.unwrap_or_else(|| self.can_compact(options, path, place));
+                // Leading commas are aligned with the opening bracket, the items
+                // follow the commas.
+                let separators = options.separators(false);
+                let leading = !compact && options.leading_commas();
+                let (indent, child_indent) = if leading {
+                    (place.column, place.column + separators.leading().len())
+                } else {
+                    let base = match options.indent_style() {
+                        IndentStyle::Block => place.indent,
+                        IndentStyle::Hanging => place.column,
+                    };
+                    let indent = options.indent_at(path, container.level) as usize;
+
+                    (base, base + indent)
+                };
+                let max_len = options
+                    .max_len_at(path, container.level)
+                    .map(|max| max as usize);
+                // With leading commas the first line of items starts at the
+                // indentation of the opening bracket.
+                let line = if leading { place.indent } else { child_indent };
+                // lines of aligned items must also respect the ribbon width
+                let limit = max_len.map(|max| match options.ribbon_width() {
+                    Some(ribbon) => cmp::min(max, line + ribbon as usize),
+                    None => max,
+                });
+                // the closing bracket follows the last item on the same line
+                let padding = options.padding(container.token.is_empty());
+                // With leading commas the items are aligned with the commas, so
+                // the brackets are padded to the width of the comma.
+                let gap = if leading {
+                    separators.leading().len() - 1
+                } else {
+                    padding
+                };
+                let tail = if !compact && options.lisp_closing() {
+                    Some(place.suffix + 1 + gap)
+                } else {
+                    None
+                };
+                // the width of what follows the last item in a line, either a
+                // comma or the closing brackets
+                let last = cmp::max(tail.unwrap_or(0), separators.comma().len());
+                let scalars = || container.token.iter().all(|t| Kind::of(t) == Kind::Scalar);
+                let cells = match limit {
+                    Some(max) if !compact && !object && options.array_grid() && scalars() => {
+                        align::grid(
+                            &container.token,
+                            options,
+                            child_indent,
+                            max.saturating_sub(last),
+                        )
                     }
+                    _ => None,
+                };
+                let rows = if compact || object {
+                    None
+                } else {
+                    let matrix = || align::matrix(&container.token, options);
+                    let table = || align::table(&container.token, options);
+                    let place = Place {
+                        indent: line,
+                        column: child_indent,
+                        suffix: last,
+                    };
+
+                    // the rows are only used, if each of them can be compacted
+                    options
+                        .matrix()
+                        .then(matrix)
+                        .flatten()
+                        .or_else(|| options.table().then(table).flatten())
+                        .filter(|rows| self.compacts_rows(rows, options, path, place))
+                };
+                let fill = if object {
+                    options.object_fill()
+                } else {
+                    options.array_fill()
+                };
+                let fill = !compact && cells.is_none() && rows.is_none() && fill.applies(self);
+                let cells = match cells {
+                    None if !compact && !object && !fill => {
+                        let max = limit.map_or(usize::MAX, |max| max.saturating_sub(last));
+
+                        align::numbers(&container.token, options, child_indent, max)
+                    }
+                    cells => cells,
+                };
+                let keys = if object && !compact && !fill && options.align_values() {
+                    let model = options.width_model();
+
+                    container
+                        .token
+                        .iter()
+                        .step_by(2)
+                        .map(|t| t.length(model))
+                        .max()
+                } else {
+                    None
+                };
+                let hug = if !compact && rows.is_none() && options.hugging() {
+                    self.hug(options, path, child_indent)
+                        .then_some(place.column + 1 + gap)
+                } else {
+                    None
+                };
 
-                    first = false;
-                }
+                writer.write_all(if object { b"{" } else { b"[" })?;
 
-                if compact && first {
-                    writer.write_all(b"}")?;
-                } else if compact && !first {
-                    writer.write_all(b" }")?;
+                if compact || leading || hug.is_some() {
+                    write_indent!(writer, gap);
                 } else {
                     writer.write_all(b"\n")?;
-                    write_indent!(writer, spaces);
-                    writer.write_all(b"}")?;
                 }
-            }
-        };
 
-        Ok(())
+                stack.push(Frame {
+                    container,
+                    object,
+                    compact,
+                    indent,
+                    child_indent,
+                    fill,
+                    max_len,
+                    column: None,
+                    cells,
+                    rows,
+                    pad: 0,
+                    keys,
+                    hug,
+                    tail,
+                    padding: if container.token.is_empty() { 0 } else { gap },
+                    line: place.indent,
+                    leading,
+                    next: 0,
+                });
+
+                Ok(true)
+            }
+        }
     }
 
-    fn can_compact(&self, options: &Options, forced_indent: Option<usize>) -> bool {
+    fn can_compact<'a>(
+        &'a self,
+        options: &Options,
+        path: &mut Vec<Segment<'a>>,
+        place: Place,
+    ) -> bool {
         match self {
             Token::BeginObject(_)
             | Token::EndObject
             | Token::BeginArray(_)
             | Token::EndArray
             | Token::Data(_) => true,
-            Token::Array(level, _) | Token::Object(level, _) => {
-                options.max_len().is_some_and(|max| {
-                    let prefix =
-                        forced_indent.unwrap_or_else(|| (level * options.indent()) as usize);
+            // an empty array or object is always written in its compact form
+            Token::Array(c) | Token::Object(c) if c.token.is_empty() => true,
+            Token::Array(c) | Token::Object(c) => {
+                if let Some(layout) = options.rule(path) {
+                    return layout == Layout::Compact;
+                }
+
+                if let Some(layout) = options.depth_layout(c.level) {
+                    // Once compacted, nested containers cannot be expanded anymore.
+                    return layout == Layout::Compact && !options.expands_below(path, self);
+                }
+
+                let ctx = LayoutContext::new(
+                    self,
+                    c,
+                    path,
+                    options,
+                    place.indent,
+                    place.column,
+                    place.suffix,
+                );
+
+                c.inline
+                    && options.policy().layout(&ctx) == Layout::Compact
+                    && self.compacts_below(options, path, place)
+            }
+        }
+    }
+
+    /// Tests whether all arrays and objects nested in the container can be
+    /// compacted, if the container is compacted at `place`.
+    ///
+    /// Rules, depth limits and the policy are applied to each nested array
+    /// and object at the position, where it is written in the line. So the
+    /// line must not exceed the maximum line length of any of them.
+    fn compacts_below<'a>(
+        &'a self,
+        options: &Options,
+        path: &mut Vec<Segment<'a>>,
+        place: Place,
+    ) -> bool {
+        let model = options.width_model();
+        let separators = options.separators(true);
+        let end = place.column + self.length(model) + place.suffix;
+        let len = path.len();
+        let mut stack = vec![(self, place.column, len)];
+        let mut compact = true;
+
+        'outer: while let Some((token, column, depth)) = stack.pop() {
+            let (object, container) = match token {
+                Token::Array(c) => (false, c),
+                Token::Object(c) => (true, c),
+                _ => continue,
+            };
+            let step = if object { 2 } else { 1 };
+            let mut column = column + 1 + options.padding(false);
+
+            for (idx, item) in container.token.chunks(step).enumerate() {
+                let (segment, t) = match item {
+                    [key, t] => {
+                        column += key.length(model) + separators.key.len();
+
+                        (Segment::Key(key.as_data().unwrap_or_default()), t)
+                    }
+                    [t] => (Segment::Index(idx), t),
+                    _ => continue,
+                };
+                let start = column;
+
+                column += t.length(model) + separators.item.len();
+
+                let c = match t {
+                    Token::Array(c) | Token::Object(c) if !c.token.is_empty() => c,
+                    _ => continue,
+                };
+
+                path.truncate(depth);
+                path.push(segment);
+
+                if let Some(layout) = options.rule(path) {
+                    compact = layout == Layout::Compact;
+                } else if let Some(layout) = options.depth_layout(c.level) {
+                    compact = layout == Layout::Compact && !options.expands_below(path, t);
+                } else {
+                    let suffix = end.saturating_sub(start + t.length(model));
+                    let ctx = LayoutContext::new(t, c, path, options, place.indent, start, suffix);
+
+                    compact = options.policy().layout(&ctx) == Layout::Compact;
+
+                    // the policy is consulted for the nested items, too
+                    if compact {
+                        stack.push((t, start, path.len()));
+                    }
+                }
 
-                    prefix + self.length() < max as usize
-                })
+                if !compact {
+                    break 'outer;
+                }
             }
         }
+
+        path.truncate(len);
+
+        compact
     }
 
     fn debug_info(&self) -> &'static str {
@@ -272,34 +827,117 @@ impl Token {
             Self::BeginArray(_) => "BeginArray",
             Self::EndArray => "EndArray",
             Self::Data(_) => "Data",
-            Self::Array(_, _) => "Array",
-            Self::Object(_, _) => "Object",
+            Self::Array(_) => "Array",
+            Self::Object(_) => "Object",
         }
     }
 }
 
 impl Display for Token {
     fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
-        match self {
-            Token::BeginObject(_) => Ok(()),
-            Token::EndObject => Ok(()),
-            Token::BeginArray(_) => Ok(()),
-            Token::EndArray => Ok(()),
-            Token::Data(vec) => {
-                write!(fmt, "{}", String::from_utf8_lossy(vec))
-            }
-            Token::Array(_, token) => {
-                let vec = token.iter().map(|t| t.to_string()).collect::<Vec<_>>();
+        let mut vec = vec![];
 
-                write!(fmt, "[ {} ]", vec.join(", "))
-            }
-            Token::Object(_, token) => {
-                let vec = token
-                    .chunks_exact(2)
-                    .map(|c| format!("{}: {}", c[0], c[1]))
-                    .collect::<Vec<_>>();
+        self.format(&mut vec, &Options::default(), Some(true), 0)
+            .map_err(|_| fmt::Error)?;
+
+        write!(fmt, "{}", String::from_utf8_lossy(&vec))
+    }
+}
+
+/// Where a token is placed in the output.
+#[derive(Clone, Copy)]
+struct Place {
+    /// The indentation of the line, where the token starts.
+    indent: usize,
+
+    /// The column, where the token starts.
+    column: usize,
+
+    /// The width of what follows the token on the same line.
+    suffix: usize,
+}
+
+/// An array or object, which is currently written by [`Token::format`].
+struct Frame<'a> {
+    container: &'a Container,
+    object: bool,
+    compact: bool,
+    indent: usize,
+    child_indent: usize,
+
+    /// Whether several items are packed into one line.
+    fill: bool,
+    max_len: Option<usize>,
+
+    /// The column after the last item, if the next item can be appended to
+    /// the same line.
+    column: Option<usize>,
+
+    /// The columns, if the items are aligned.
+    cells: Option<Cells>,
+
+    /// The columns of the items, if they are arrays or objects aligned to
+    /// each other.
+    rows: Option<Cells>,
+
+    /// The padding of the last item up to the width of its column.
+    pad: usize,
+
+    /// The width of the widest key, if the values of an object are aligned.
+    keys: Option<usize>,
+
+    /// The column of the hugged item.
+    hug: Option<usize>,
+
+    /// The width of the closing brackets following the last item, if they
+    /// are written on the same line.
+    tail: Option<usize>,
+
+    /// The spaces written in front of the closing bracket of a compact
+    /// array or object.
+    padding: usize,
+
+    /// Whether the comma is written in front of an item in a new line.
+    leading: bool,
+
+    /// The indentation of the current line.
+    line: usize,
+
+    next: usize,
+}
+
+impl<'a> Frame<'a> {
+    fn close<W: ?Sized + io::Write>(&self, writer: &mut W) -> io::Result<()> {
+        let close = if self.object { b"}" } else { b"]" };
+
+        if self.compact || self.hug.is_some() {
+            let spaces = self.pad + self.padding;
+
+            write_indent!(writer, spaces);
+        } else if self.tail.is_some() {
+            let spaces = self.padding;
+
+            write_indent!(writer, spaces);
+        } else {
+            let spaces = self.indent;
+
+            writer.write_all(b"\n")?;
+            write_indent!(writer, spaces);
+        }
+
+        writer.write_all(close)
+    }
+}
+
+impl Drop for Container {
+    fn drop(&mut self) {
+        // Release nested containers one after another, otherwise a deeply
+        // nested container would overflow the stack.
+        let mut stack = mem::take(&mut self.token);
 
-                write!(fmt, "{{ {} }}", vec.join(", "))
+        while let Some(t) = stack.pop() {
+            if let Token::Array(mut c) | Token::Object(mut c) = t {
+                stack.append(&mut c.token);
             }
         }
     }
@@ -21,7 +21,9 @@
 // SOFTWARE.
 
 use crate::error::Error;
+use crate::options::Options;
 use crate::token::Token;
+use crate::width::WidthModel;
 
 #[test]
 fn as_begin_object_begin_object() {
@@ -318,3 +320,143 @@ fn as_data_mut_err_data() {
 
     assert_eq!(t.as_data_mut_err().unwrap(), &mut Vec::<u8>::new());
 }
+
+fn data(s: &str) -> Token {
+    Token::Data(s.as_bytes().to_vec())
+}
+
+#[test]
+fn length_data() {
+    let t = data("\"名前\"");
+
+    assert_eq!(t.length(WidthModel::Unicode), 6);
+    assert_eq!(t.length(WidthModel::Bytes), 8);
+}
+
+#[test]
+fn length_empty_array() {
+    let t = Token::array(0, vec![], &Options::default());
+
+    assert_eq!(t.length(WidthModel::Unicode), 3);
+}
+
+#[test]
+fn length_array() {
+    let t = Token::array(0, vec![data("1"), data("22")], &Options::default());
+
+    // [ 1, 22 ]
+    assert_eq!(t.length(WidthModel::Unicode), 9);
+}
+
+#[test]
+fn length_nested_array() {
+    let options = Options::default();
+    let inner = Token::array(1, vec![data("1"), data("22")], &options);
+    let t = Token::array(0, vec![inner, data("3")], &options);
+
+    // [ [ 1, 22 ], 3 ]
+    assert_eq!(t.length(WidthModel::Unicode), 16);
+}
+
+#[test]
+fn length_empty_object() {
+    let t = Token::object(0, vec![], &Options::default());
+
+    assert_eq!(t.length(WidthModel::Unicode), 3);
+}
+
+#[test]
+fn length_object() {
+    let t = Token::object(
+        0,
+        vec![data("\"a\""), data("1"), data("\"b\""), data("22")],
+        &Options::default(),
+    );
+
+    // { "a": 1, "b": 22 }
+    assert_eq!(t.length(WidthModel::Unicode), 19);
+}
+
+#[test]
+fn length_capped() {
+    let mut options = Options::default();
+
+    options.set_max_len(5);
+
+    let t = Token::array(0, vec![data("1"), data("22"), data("333")], &options);
+
+    assert_eq!(t.length(WidthModel::Unicode), 6);
+}
+
+fn is_inline(t: &Token) -> bool {
+    match t {
+        Token::Array(c) | Token::Object(c) => c.inline,
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn inline_unlimited() {
+    let t = Token::array(
+        0,
+        vec![data("1"), data("2"), data("3")],
+        &Options::default(),
+    );
+
+    assert!(is_inline(&t));
+}
+
+#[test]
+fn inline_array_items() {
+    let mut options = Options::default();
+
+    options.set_max_inline_items(2, 1);
+
+    let t = Token::array(0, vec![data("1"), data("2")], &options);
+    assert!(is_inline(&t));
+
+    let t = Token::array(0, vec![data("1"), data("2"), data("3")], &options);
+    assert!(!is_inline(&t));
+}
+
+#[test]
+fn inline_object_items() {
+    let mut options = Options::default();
+
+    options.set_max_inline_items(2, 1);
+
+    let t = Token::object(0, vec![data("\"a\""), data("1")], &options);
+    assert!(is_inline(&t));
+
+    let t = Token::object(
+        0,
+        vec![data("\"a\""), data("1"), data("\"b\""), data("2")],
+        &options,
+    );
+    assert!(!is_inline(&t));
+}
+
+#[test]
+fn inline_nested() {
+    let mut options = Options::default();
+
+    options.set_max_inline_items(2, 1);
+
+    let inner = Token::array(1, vec![data("1"), data("2"), data("3")], &options);
+    let t = Token::array(0, vec![inner], &options);
+
+    assert!(!is_inline(&t));
+}
+
+#[test]
+fn inline_leaf_only() {
+    let mut options = Options::default();
+
+    options.set_leaf_only(true);
+
+    let inner = Token::array(1, vec![data("1"), data("2")], &options);
+    assert!(is_inline(&inner));
+
+    let t = Token::object(0, vec![data("\"a\""), inner], &options);
+    assert!(!is_inline(&t));
+}
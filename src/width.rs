@@ -0,0 +1,56 @@
+// MIT License
+//
+// Copyright (c) 2024 Robin Doer
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+#[cfg(test)]
+mod tests;
+
+use std::str;
+use unicode_width::UnicodeWidthStr;
+
+/// Defines how the width of formatted JSON is measured.
+///
+/// The width of a line is compared against the maximum line length to decide
+/// whether an array or object can be compacted.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum WidthModel {
+    /// Measures the width in Unicode display columns.
+    ///
+    /// East Asian wide characters occupy two columns, zero-width characters
+    /// like combining marks do not occupy any column.
+    #[default]
+    Unicode,
+
+    /// Measures the width in UTF-8 encoded bytes.
+    Bytes,
+}
+
+impl WidthModel {
+    /// Returns the width of the given UTF-8 encoded `data`.
+    ///
+    /// Falls back to the number of bytes, if `data` is not valid UTF-8.
+    pub(crate) fn measure(&self, data: &[u8]) -> usize {
+        match self {
+            Self::Unicode => str::from_utf8(data).map_or(data.len(), |s| s.width()),
+            Self::Bytes => data.len(),
+        }
+    }
+}
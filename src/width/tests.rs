@@ -0,0 +1,63 @@
+// MIT License
+//
+// Copyright (c) 2024 Robin Doer
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::width::WidthModel;
+
+#[test]
+fn default() {
+    assert_eq!(WidthModel::default(), WidthModel::Unicode);
+}
+
+#[test]
+fn unicode_ascii() {
+    assert_eq!(WidthModel::Unicode.measure(b"\"abc\""), 5);
+}
+
+#[test]
+fn unicode_wide() {
+    assert_eq!(WidthModel::Unicode.measure("\"名前\"".as_bytes()), 6);
+}
+
+#[test]
+fn unicode_combining() {
+    assert_eq!(WidthModel::Unicode.measure("\"e\u{301}\"".as_bytes()), 3);
+}
+
+#[test]
+fn unicode_invalid() {
+    assert_eq!(WidthModel::Unicode.measure(b"\"\xff\""), 3);
+}
+
+#[test]
+fn bytes_ascii() {
+    assert_eq!(WidthModel::Bytes.measure(b"\"abc\""), 5);
+}
+
+#[test]
+fn bytes_wide() {
+    assert_eq!(WidthModel::Bytes.measure("\"名前\"".as_bytes()), 8);
+}
+
+#[test]
+fn bytes_combining() {
+    assert_eq!(WidthModel::Bytes.measure("\"e\u{301}\"".as_bytes()), 5);
+}
@@ -0,0 +1,86 @@
+// MIT License
+//
+// Copyright (c) 2024 Robin Doer
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+mod common;
+
+use json_pretty_compact::{Fill, PrettyCompactFormatter};
+
+use crate::common::*;
+
+#[test]
+fn aligned() {
+    let value = parse_json_string(
+        r#"{ "id": 1, "name": "Joe", "tags": ["a", "b"], "nested": { "x": 1, "long_key": [1, 2, 3] } }"#,
+    );
+    let formatter = PrettyCompactFormatter::new()
+        .with_max_line_length(30)
+        .with_aligned_values(true);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(
+        json,
+        r#"{
+  "id":     1,
+  "name":   "Joe",
+  "tags":   [ "a", "b" ],
+  "nested": {
+    "x":        1,
+    "long_key": [ 1, 2, 3 ]
+  }
+}"#
+    );
+}
+
+#[test]
+fn width() {
+    // "x": [ 1, 2, 3, 4 ], fits into 28 columns, but not if aligned
+    let value = parse_json_string(r#"{ "x": [1, 2, 3, 4], "long_key": 1 }"#);
+    let formatter = PrettyCompactFormatter::new()
+        .with_max_line_length(28)
+        .with_aligned_values(true);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(
+        json,
+        r#"{
+  "x":        [
+    1,
+    2,
+    3,
+    4
+  ],
+  "long_key": 1
+}"#
+    );
+}
+
+#[test]
+fn fill() {
+    let value = parse_json_string(r#"{ "a": 1, "bbbbbb": 2, "c": 3 }"#);
+    let formatter = PrettyCompactFormatter::new()
+        .with_max_line_length(21)
+        .with_object_fill(Fill::All)
+        .with_aligned_values(true);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(json, "{\n  \"a\": 1,\n  \"bbbbbb\": 2, \"c\": 3\n}");
+}
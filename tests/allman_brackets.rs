@@ -0,0 +1,98 @@
+// MIT License
+//
+// Copyright (c) 2024 Robin Doer
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+mod common;
+
+use json_pretty_compact::PrettyCompactFormatter;
+
+use crate::common::*;
+
+const VALUE: &str = r#"{ "a": 1, "b": [1, 2, 3, 4], "c": [{ "x": 1, "y": [1, 2] }] }"#;
+
+#[test]
+fn allman() {
+    let value = parse_json_string(VALUE);
+    let formatter = PrettyCompactFormatter::new()
+        .with_max_line_length(19)
+        .with_allman_brackets(true);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(
+        json,
+        r#"{
+  "a": 1,
+  "b":
+  [
+    1,
+    2,
+    3,
+    4
+  ],
+  "c":
+  [
+    {
+      "x": 1,
+      "y": [ 1, 2 ]
+    }
+  ]
+}"#
+    );
+}
+
+#[test]
+fn leading_commas() {
+    let value = parse_json_string(VALUE);
+    let formatter = PrettyCompactFormatter::new()
+        .with_max_line_length(19)
+        .with_allman_brackets(true)
+        .with_leading_commas(true);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(
+        json,
+        r#"{ "a": 1
+, "b":
+  [ 1
+  , 2
+  , 3
+  , 4
+  ]
+, "c":
+  [ { "x": 1
+    , "y": [ 1, 2 ]
+    }
+  ]
+}"#
+    );
+}
+
+#[test]
+fn hugging() {
+    let value = parse_json_string(r#"{ "data": [1, 2, 3, 4, 5] }"#);
+    let formatter = PrettyCompactFormatter::new()
+        .with_max_line_length(16)
+        .with_allman_brackets(true)
+        .with_hugging(true);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(json, "{ \"data\": [\n  1,\n  2,\n  3,\n  4,\n  5\n] }");
+}
@@ -20,6 +20,9 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
+// Not every test uses every helper.
+#![allow(dead_code, unused_imports, unused_macros)]
+
 use json_pretty_compact::PrettyCompactFormatter;
 use serde::Serialize;
 use serde_json::{Serializer, Value};
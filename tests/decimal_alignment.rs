@@ -0,0 +1,128 @@
+// MIT License
+//
+// Copyright (c) 2024 Robin Doer
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+mod common;
+
+use json_pretty_compact::{Fill, PrettyCompactFormatter};
+
+use crate::common::*;
+
+#[test]
+fn array() {
+    let value = parse_json_string(r#"{ "amount": [1.5, 100, -2.25, 30.125] }"#);
+    let formatter = PrettyCompactFormatter::new()
+        .with_max_line_length(20)
+        .with_decimal_alignment(true);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(
+        json,
+        r#"{
+  "amount": [
+      1.5,
+    100,
+     -2.25,
+     30.125
+  ]
+}"#
+    );
+}
+
+#[test]
+fn mixed() {
+    let value = parse_json_string(r#"[1.5, 100, "-2.25", 30.125]"#);
+    let formatter = PrettyCompactFormatter::new()
+        .with_max_line_length(20)
+        .with_decimal_alignment(true);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(json, "[\n  1.5,\n  100,\n  \"-2.25\",\n  30.125\n]");
+}
+
+#[test]
+fn fill() {
+    let value = parse_json_string(r#"[1.5, 100, -2.25, 30.125]"#);
+    let formatter = PrettyCompactFormatter::new()
+        .with_max_line_length(16)
+        .with_array_fill(Fill::All)
+        .with_decimal_alignment(true);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(json, "[\n  1.5, 100,\n  -2.25, 30.125\n]");
+}
+
+#[test]
+fn matrix() {
+    let value = parse_json_string(r#"[[1.5, 2], [10, 0.25]]"#);
+    let formatter = PrettyCompactFormatter::new()
+        .with_max_line_length(20)
+        .with_matrix(true)
+        .with_decimal_alignment(true);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(
+        json,
+        r#"[
+  [  1.5, 2    ],
+  [ 10,   0.25 ]
+]"#
+    );
+}
+
+#[test]
+fn table() {
+    let value = parse_json_string(
+        r#"[{ "what": "Beer", "amount": 18.5 }, { "what": "Car", "amount": 120.25 }]"#,
+    );
+    let formatter = PrettyCompactFormatter::new()
+        .with_max_line_length(40)
+        .with_table(true)
+        .with_decimal_alignment(true);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(
+        json,
+        r#"[
+  { "what": "Beer", "amount":  18.5  },
+  { "what": "Car",  "amount": 120.25 }
+]"#
+    );
+}
+
+#[test]
+fn grid() {
+    let value = parse_json_string(r#"[1.5, 22, 3, 4.25, 5, 6, 7, 8, 9, 10, 11, 12.5]"#);
+    let formatter = PrettyCompactFormatter::new()
+        .with_max_line_length(22)
+        .with_array_grid(true)
+        .with_decimal_alignment(true);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(
+        json,
+        r#"[
+  1.5, 22,  3,  4.25,
+  5,    6,  7,  8,
+  9,   10, 11, 12.5
+]"#
+    );
+}
@@ -0,0 +1,94 @@
+// MIT License
+//
+// Copyright (c) 2024 Robin Doer
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+mod common;
+
+use json_pretty_compact::{Layout, PrettyCompactFormatter};
+
+use crate::common::*;
+
+const JSON: &str = r#"{ "a": { "b": [1, 2], "c": { "d": [3, 4, 5, 6, 7, 8, 9] } }, "e": [] }"#;
+
+#[test]
+fn min_compact_depth() {
+    let value = parse_json_string(JSON);
+    let formatter = PrettyCompactFormatter::new().with_min_compact_depth(1);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(
+        json,
+        r#"{
+  "a": { "b": [ 1, 2 ], "c": { "d": [ 3, 4, 5, 6, 7, 8, 9 ] } },
+  "e": [ ]
+}"#
+    );
+}
+
+#[test]
+fn max_expanded_depth() {
+    let value = parse_json_string(JSON);
+    let formatter = PrettyCompactFormatter::new()
+        .with_max_line_length(10)
+        .with_max_expanded_depth(1);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(
+        json,
+        r#"{
+  "a": {
+    "b": [ 1, 2 ],
+    "c": { "d": [ 3, 4, 5, 6, 7, 8, 9 ] }
+  },
+  "e": [ ]
+}"#
+    );
+}
+
+#[test]
+fn rule_wins() {
+    let value = parse_json_string(JSON);
+    let formatter = PrettyCompactFormatter::new()
+        .with_max_expanded_depth(0)
+        .with_rule("/a/c/d", Layout::Expanded);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(
+        json,
+        r#"{
+  "a": {
+    "b": [ 1, 2 ],
+    "c": {
+      "d": [
+        3,
+        4,
+        5,
+        6,
+        7,
+        8,
+        9
+      ]
+    }
+  },
+  "e": [ ]
+}"#
+    );
+}
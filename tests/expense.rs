@@ -34,3 +34,10 @@ t!(
 );
 
 t!(default, "expense", "default", PrettyCompactFormatter::new());
+
+t!(
+    table,
+    "expense",
+    "table",
+    PrettyCompactFormatter::new().with_table(true)
+);
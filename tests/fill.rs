@@ -0,0 +1,206 @@
+// MIT License
+//
+// Copyright (c) 2024 Robin Doer
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+mod common;
+
+use json_pretty_compact::{Fill, PrettyCompactFormatter};
+
+use crate::common::*;
+
+const NUMBERS: &str = "[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]";
+const MIXED: &str =
+    "[1, 2, 3, 4, 5, 6, 7, 8, [1, 2], 9, 10, 11, 12, [1, 2, 3, 4, 5, 6, 7, 8, 9, 10], 13]";
+
+#[test]
+fn off() {
+    let value = parse_json_string("[1, 2, 3]");
+    let formatter = PrettyCompactFormatter::new()
+        .with_max_line_length(5)
+        .with_array_fill(Fill::Off);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(json, "[\n  1,\n  2,\n  3\n]");
+}
+
+#[test]
+fn scalars() {
+    let value = parse_json_string(NUMBERS);
+    let formatter = PrettyCompactFormatter::new()
+        .with_max_line_length(20)
+        .with_array_fill(Fill::Scalars);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(
+        json,
+        r#"[
+  1, 2, 3, 4, 5, 6,
+  7, 8, 9, 10, 11,
+  12, 13, 14, 15, 16
+]"#
+    );
+}
+
+#[test]
+fn scalars_mixed() {
+    let value = parse_json_string(MIXED);
+    let formatter = PrettyCompactFormatter::new()
+        .with_max_line_length(20)
+        .with_array_fill(Fill::Scalars);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(
+        json,
+        r#"[
+  1,
+  2,
+  3,
+  4,
+  5,
+  6,
+  7,
+  8,
+  [ 1, 2 ],
+  9,
+  10,
+  11,
+  12,
+  [
+    1, 2, 3, 4, 5,
+    6, 7, 8, 9, 10
+  ],
+  13
+]"#
+    );
+}
+
+#[test]
+fn all() {
+    let value = parse_json_string(MIXED);
+    let formatter = PrettyCompactFormatter::new()
+        .with_max_line_length(20)
+        .with_array_fill(Fill::All);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(
+        json,
+        r#"[
+  1, 2, 3, 4, 5, 6,
+  7, 8, [ 1, 2 ], 9,
+  10, 11, 12,
+  [
+    1, 2, 3, 4, 5,
+    6, 7, 8, 9, 10
+  ],
+  13
+]"#
+    );
+}
+
+#[test]
+fn nested() {
+    let value = parse_json_string(&format!(r#"{{ "numbers": {} }}"#, NUMBERS));
+    let formatter = PrettyCompactFormatter::new()
+        .with_max_line_length(20)
+        .with_array_fill(Fill::Scalars);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(
+        json,
+        r#"{
+  "numbers": [
+    1, 2, 3, 4, 5,
+    6, 7, 8, 9, 10,
+    11, 12, 13, 14,
+    15, 16
+  ]
+}"#
+    );
+}
+
+#[test]
+fn unlimited() {
+    let value = parse_json_string("[1, [2, 3]]");
+    let formatter = PrettyCompactFormatter::no_rules()
+        .with_min_compact_depth(2)
+        .with_array_fill(Fill::All);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(json, "[\n  1,\n  [\n    2,\n    3\n  ]\n]");
+}
+
+#[test]
+fn object_scalars() {
+    let value = parse_json_string(r#"{ "a": true, "b": false, "c": true, "d": null, "e": 1 }"#);
+    let formatter = PrettyCompactFormatter::new()
+        .with_max_line_length(30)
+        .with_object_fill(Fill::Scalars);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(
+        json,
+        r#"{
+  "a": true, "b": false,
+  "c": true, "d": null, "e": 1
+}"#
+    );
+}
+
+#[test]
+fn object_scalars_mixed() {
+    let value = parse_json_string(r#"{ "a": true, "b": false, "c": [1] }"#);
+    let formatter = PrettyCompactFormatter::new()
+        .with_max_line_length(30)
+        .with_object_fill(Fill::Scalars);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(
+        json,
+        r#"{
+  "a": true,
+  "b": false,
+  "c": [ 1 ]
+}"#
+    );
+}
+
+#[test]
+fn object_all() {
+    let value = parse_json_string(
+        r#"{ "a": true, "b": false, "c": [1, 2], "d": { "x": [1, 2, 3, 4, 5] }, "e": 1, "f": 2 }"#,
+    );
+    let formatter = PrettyCompactFormatter::new()
+        .with_max_line_length(30)
+        .with_object_fill(Fill::All);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(
+        json,
+        r#"{
+  "a": true, "b": false,
+  "c": [ 1, 2 ],
+  "d": {
+    "x": [ 1, 2, 3, 4, 5 ]
+  },
+  "e": 1, "f": 2
+}"#
+    );
+}
@@ -0,0 +1,93 @@
+// MIT License
+//
+// Copyright (c) 2024 Robin Doer
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+mod common;
+
+use json_pretty_compact::{Fill, PrettyCompactFormatter};
+
+use crate::common::*;
+
+const TAGS: &str = r#"{
+  "tags": ["alpha", "beta", "gamma", "delta", "epsilon", "zeta", "eta", "theta", "iota", "kappa"],
+  "numbers": [1, 22, 333, 4444, 5, 66, 777, 8888, 9, 10, 11]
+}"#;
+
+#[test]
+fn grid() {
+    let value = parse_json_string(TAGS);
+    let formatter = PrettyCompactFormatter::new()
+        .with_max_line_length(30)
+        .with_array_grid(true);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(
+        json,
+        r#"{
+  "tags": [
+    "alpha",   "beta",
+    "gamma",   "delta",
+    "epsilon", "zeta",
+    "eta",     "theta",
+    "iota",    "kappa"
+  ],
+  "numbers": [
+    1,  22,  333,  4444, 5,
+    66, 777, 8888, 9,    10,
+    11
+  ]
+}"#
+    );
+}
+
+#[test]
+fn precedence() {
+    let value = parse_json_string(TAGS);
+    let formatter = PrettyCompactFormatter::new()
+        .with_max_line_length(30)
+        .with_array_fill(Fill::All)
+        .with_array_grid(true);
+    let json = serialize_to_string(&value, formatter);
+
+    assert!(json.contains("    \"alpha\",   \"beta\",\n"));
+}
+
+#[test]
+fn mixed() {
+    let value = parse_json_string(r#"[1, 2, 3, [4]]"#);
+    let formatter = PrettyCompactFormatter::new()
+        .with_max_line_length(10)
+        .with_array_grid(true);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(json, "[\n  1,\n  2,\n  3,\n  [ 4 ]\n]");
+}
+
+#[test]
+fn narrow() {
+    let value = parse_json_string(r#"["alpha", "beta"]"#);
+    let formatter = PrettyCompactFormatter::new()
+        .with_max_line_length(12)
+        .with_array_grid(true);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(json, "[\n  \"alpha\",\n  \"beta\"\n]");
+}
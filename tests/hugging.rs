@@ -0,0 +1,110 @@
+// MIT License
+//
+// Copyright (c) 2024 Robin Doer
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+mod common;
+
+use json_pretty_compact::{Layout, PrettyCompactFormatter};
+
+use crate::common::*;
+
+#[test]
+fn array() {
+    let value = parse_json_string(r#"[{ "a": 1, "b": [1, 2, 3, 4] }]"#);
+    let formatter = PrettyCompactFormatter::new()
+        .with_max_line_length(21)
+        .with_hugging(true);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(
+        json,
+        r#"[ {
+  "a": 1,
+  "b": [ 1, 2, 3, 4 ]
+} ]"#
+    );
+}
+
+#[test]
+fn object() {
+    let value =
+        parse_json_string(r#"{ "response": { "data": [{ "id": 1 }, { "id": 2 }] }, "x": 1 }"#);
+    let formatter = PrettyCompactFormatter::new()
+        .with_max_line_length(20)
+        .with_hugging(true);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(
+        json,
+        r#"{
+  "response": { "data": [
+    { "id": 1 },
+    { "id": 2 }
+  ] },
+  "x": 1
+}"#
+    );
+}
+
+#[test]
+fn compact_item() {
+    let value = parse_json_string(r#"[[1, 2]]"#);
+    let formatter = PrettyCompactFormatter::new()
+        .with_rule("", Layout::Expanded)
+        .with_hugging(true);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(json, "[\n  [ 1, 2 ]\n]");
+}
+
+#[test]
+fn scalar() {
+    let value = parse_json_string(r#"{ "a": "a long string value" }"#);
+    let formatter = PrettyCompactFormatter::new()
+        .with_max_line_length(20)
+        .with_hugging(true);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(json, "{\n  \"a\": \"a long string value\"\n}");
+}
+
+#[test]
+fn several_items() {
+    let value = parse_json_string(r#"[{ "a": [1, 2, 3, 4, 5] }, 1]"#);
+    let formatter = PrettyCompactFormatter::new()
+        .with_max_line_length(20)
+        .with_hugging(true);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(
+        json,
+        r#"[
+  { "a": [
+    1,
+    2,
+    3,
+    4,
+    5
+  ] },
+  1
+]"#
+    );
+}
@@ -0,0 +1,147 @@
+// MIT License
+//
+// Copyright (c) 2024 Robin Doer
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+mod common;
+
+use json_pretty_compact::{Kind, Layout, LayoutContext, PrettyCompactFormatter};
+use std::panic;
+use std::sync::{Arc, Mutex};
+
+use crate::common::*;
+
+const JSON: &str = r#"{ "a": [1, 2, 3], "b": { "c": [{ "d/e": 1 }] } }"#;
+
+#[test]
+fn default() {
+    let value = parse_json_string(JSON);
+    let formatter = PrettyCompactFormatter::new().with_policy(|_: &LayoutContext| Layout::Default);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(
+        json,
+        r#"{ "a": [ 1, 2, 3 ], "b": { "c": [ { "d/e": 1 } ] } }"#
+    );
+}
+
+#[test]
+fn expand_objects() {
+    let value = parse_json_string(JSON);
+    let formatter = PrettyCompactFormatter::new().with_policy(|ctx: &LayoutContext| {
+        if ctx.kind() == Kind::Object {
+            Layout::Expanded
+        } else {
+            Layout::Default
+        }
+    });
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(
+        json,
+        r#"{
+  "a": [ 1, 2, 3 ],
+  "b": {
+    "c": [
+      {
+        "d/e": 1
+      }
+    ]
+  }
+}"#
+    );
+}
+
+#[test]
+fn nested() {
+    // the example of the LayoutPolicy documentation
+    let value = parse_json_string(r#"{ "a": [{ "b": 1 }] }"#);
+    let formatter = PrettyCompactFormatter::new().with_policy(|ctx: &LayoutContext| {
+        if ctx.kind() == Kind::Array && ctx.children().any(|k| k == Kind::Object) {
+            Layout::Expanded
+        } else {
+            Layout::Default
+        }
+    });
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(json, "{\n  \"a\": [\n    { \"b\": 1 }\n  ]\n}");
+}
+
+#[test]
+fn compact_scalar_arrays() {
+    let value = parse_json_string(JSON);
+    let formatter = PrettyCompactFormatter::no_rules().with_policy(|ctx: &LayoutContext| {
+        if ctx.children().all(|k| k == Kind::Scalar) {
+            Layout::Compact
+        } else {
+            Layout::Default
+        }
+    });
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(
+        json,
+        r#"{
+  "a": [ 1, 2, 3 ],
+  "b": {
+    "c": [
+      { "d/e": 1 }
+    ]
+  }
+}"#
+    );
+}
+
+#[test]
+fn context() {
+    let value = parse_json_string(JSON);
+    let seen = Arc::new(Mutex::new(vec![]));
+    let seen2 = seen.clone();
+    let formatter = PrettyCompactFormatter::no_rules().with_policy(move |ctx: &LayoutContext| {
+        seen2
+            .lock()
+            .unwrap()
+            .push((ctx.pointer(), ctx.depth(), ctx.len(), ctx.column()));
+        Layout::Expanded
+    });
+
+    serialize_to_string(&value, formatter);
+
+    assert_eq!(
+        *seen.lock().unwrap(),
+        [
+            ("".to_string(), 0, 2, 0),
+            ("/a".to_string(), 1, 3, 7),
+            ("/b".to_string(), 1, 1, 7),
+            ("/b/c".to_string(), 2, 1, 9),
+            ("/b/c/0".to_string(), 3, 1, 6),
+        ]
+    );
+}
+
+#[test]
+fn unwind_safe() {
+    let value = parse_json_string(JSON);
+    let formatter = PrettyCompactFormatter::new().with_policy(|_: &LayoutContext| Layout::Default);
+    let json = panic::catch_unwind(|| serialize_to_string(&value, formatter));
+
+    assert!(json.is_ok());
+}
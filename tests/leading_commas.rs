@@ -0,0 +1,94 @@
+// MIT License
+//
+// Copyright (c) 2024 Robin Doer
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+mod common;
+
+use json_pretty_compact::{Fill, PrettyCompactFormatter};
+
+use crate::common::*;
+
+#[test]
+fn array() {
+    let value = parse_json_string("[1, 2, 3]");
+    let formatter = PrettyCompactFormatter::new()
+        .with_max_line_length(8)
+        .with_leading_commas(true);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(json, "[ 1\n, 2\n, 3\n]");
+}
+
+#[test]
+fn nested() {
+    let value = parse_json_string(r#"{ "a": 1, "b": [1, 2, 3], "c": { "d": 1 } }"#);
+    let formatter = PrettyCompactFormatter::new()
+        .with_max_line_length(17)
+        .with_leading_commas(true);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(
+        json,
+        r#"{ "a": 1
+, "b": [ 1
+       , 2
+       , 3
+       ]
+, "c": { "d": 1 }
+}"#
+    );
+}
+
+#[test]
+fn width() {
+    // without a trailing comma [ 1, 2 ] fits into the line
+    let value = parse_json_string(r#"[[1, 2], [3, 4, 5]]"#);
+    let formatter = PrettyCompactFormatter::new()
+        .with_max_line_length(10)
+        .with_leading_commas(true);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(json, "[ [ 1, 2 ]\n, [ 3\n  , 4\n  , 5\n  ]\n]");
+}
+
+#[test]
+fn fill() {
+    let value = parse_json_string("[1, 2, 3, 4, 5, 6, 7]");
+    let formatter = PrettyCompactFormatter::new()
+        .with_max_line_length(12)
+        .with_array_fill(Fill::All)
+        .with_leading_commas(true);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(json, "[ 1, 2, 3, 4\n, 5, 6, 7\n]");
+}
+
+#[test]
+fn lisp_closing() {
+    let value = parse_json_string("[1, 2, 3]");
+    let formatter = PrettyCompactFormatter::new()
+        .with_max_line_length(8)
+        .with_leading_commas(true)
+        .with_lisp_closing(true);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(json, "[ 1\n, 2\n, 3 ]");
+}
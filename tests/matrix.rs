@@ -0,0 +1,180 @@
+// MIT License
+//
+// Copyright (c) 2024 Robin Doer
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+mod common;
+
+use json_pretty_compact::{Layout, LayoutContext, PrettyCompactFormatter};
+
+use crate::common::*;
+
+#[test]
+fn matrix() {
+    let value = parse_json_string(r#"{ "m": [[1, 20, 300], [4000, 5, 6], [-1.5, 0, 7]] }"#);
+    let formatter = PrettyCompactFormatter::new()
+        .with_max_line_length(30)
+        .with_matrix(true);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(
+        json,
+        r#"{
+  "m": [
+    [    1, 20, 300 ],
+    [ 4000,  5,   6 ],
+    [ -1.5,  0,   7 ]
+  ]
+}"#
+    );
+}
+
+#[test]
+fn compact() {
+    let value = parse_json_string(r#"[[1, 20], [300, 4]]"#);
+    let formatter = PrettyCompactFormatter::new().with_matrix(true);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(json, "[ [ 1, 20 ], [ 300, 4 ] ]");
+}
+
+#[test]
+fn too_wide() {
+    let value = parse_json_string(r#"[[1, 2, 3], [100000, 4, 5]]"#);
+    let formatter = PrettyCompactFormatter::new()
+        .with_max_line_length(18)
+        .with_matrix(true);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(
+        json,
+        r#"[
+  [ 1, 2, 3 ],
+  [ 100000, 4, 5 ]
+]"#
+    );
+}
+
+#[test]
+fn not_numeric() {
+    let value = parse_json_string(r#"[[1, "a"], [300, "bc"]]"#);
+    let formatter = PrettyCompactFormatter::new()
+        .with_max_line_length(15)
+        .with_matrix(true);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(
+        json,
+        r#"[
+  [ 1, "a" ],
+  [ 300, "bc" ]
+]"#
+    );
+}
+
+#[test]
+fn disabled() {
+    let value = parse_json_string(r#"[[1, 20], [300, 4]]"#);
+    let formatter = PrettyCompactFormatter::new().with_max_line_length(20);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(json, "[\n  [ 1, 20 ],\n  [ 300, 4 ]\n]");
+}
+
+const ROWS: &str = "[[1, 2, 3, 4, 5], [6, 7, 8, 9, 10]]";
+const EXPANDED: &str = r#"[
+  [
+    1,
+    2,
+    3,
+    4,
+    5
+  ],
+  [
+    6,
+    7,
+    8,
+    9,
+    10
+  ]
+]"#;
+
+#[test]
+fn rule() {
+    let value = parse_json_string(ROWS);
+    let formatter = PrettyCompactFormatter::new()
+        .with_max_line_length(30)
+        .with_matrix(true)
+        .with_rule("/0", Layout::Expanded);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(
+        json,
+        r#"[
+  [
+    1,
+    2,
+    3,
+    4,
+    5
+  ],
+  [ 6, 7, 8, 9, 10 ]
+]"#
+    );
+}
+
+#[test]
+fn min_compact_depth() {
+    let value = parse_json_string(ROWS);
+    let formatter = PrettyCompactFormatter::new()
+        .with_max_line_length(30)
+        .with_matrix(true)
+        .with_min_compact_depth(5);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(json, EXPANDED);
+}
+
+#[test]
+fn max_inline_items() {
+    let value = parse_json_string(ROWS);
+    let formatter = PrettyCompactFormatter::new()
+        .with_max_line_length(30)
+        .with_matrix(true)
+        .with_max_inline_items(3, 3);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(json, EXPANDED);
+}
+
+#[test]
+fn policy() {
+    let value = parse_json_string(ROWS);
+    let formatter = PrettyCompactFormatter::new()
+        .with_max_line_length(30)
+        .with_matrix(true)
+        .with_policy(|ctx: &LayoutContext| match ctx.depth() {
+            1 => Layout::Expanded,
+            _ => Layout::Default,
+        });
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(json, EXPANDED);
+}
@@ -0,0 +1,91 @@
+// MIT License
+//
+// Copyright (c) 2024 Robin Doer
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+mod common;
+
+use json_pretty_compact::{Error, PrettyCompactFormatter};
+use serde::Serialize;
+use serde_json::ser::Formatter;
+use serde_json::Serializer;
+
+use crate::common::*;
+
+const DEPTH: usize = 100_000;
+
+fn nested(formatter: &mut PrettyCompactFormatter, depth: usize) -> std::io::Result<Vec<u8>> {
+    let mut vec = vec![];
+
+    // Drive the formatter directly, serde itself would recurse.
+    for _ in 0..depth {
+        formatter.begin_array(&mut vec)?;
+        formatter.begin_array_value(&mut vec, true)?;
+    }
+
+    for _ in 0..depth {
+        formatter.end_array_value(&mut vec)?;
+        formatter.end_array(&mut vec)?;
+    }
+
+    Ok(vec)
+}
+
+#[test]
+fn deep_nesting() {
+    let mut formatter = PrettyCompactFormatter::new().with_indent(0);
+    let vec = nested(&mut formatter, DEPTH).unwrap();
+    let json = String::from_utf8(vec).unwrap();
+
+    assert_eq!(json.matches('[').count(), DEPTH);
+    assert_eq!(json.matches(']').count(), DEPTH);
+}
+
+#[test]
+fn deep_nesting_limited() {
+    let mut formatter = PrettyCompactFormatter::new().with_max_depth(128);
+    let err = nested(&mut formatter, DEPTH).unwrap_err();
+    let err = err.into_inner().unwrap().downcast::<Error>().unwrap();
+
+    assert!(matches!(*err, Error::DepthLimitExceeded(128)));
+}
+
+#[test]
+fn within_limit() {
+    let value = parse_json_string(r#"[[[1]], { "a": { "b": 2 } }]"#);
+    let json = serialize_to_string(&value, PrettyCompactFormatter::new().with_max_depth(3));
+
+    assert_eq!(json, r#"[ [ [ 1 ] ], { "a": { "b": 2 } } ]"#);
+}
+
+#[test]
+fn exceeds_limit() {
+    let value = parse_json_string(r#"[[[1]], { "a": { "b": 2 } }]"#);
+    let mut vec = vec![];
+    let formatter = PrettyCompactFormatter::new().with_max_depth(2);
+    let mut ser = Serializer::with_formatter(&mut vec, formatter);
+
+    let err = value.serialize(&mut ser).unwrap_err();
+
+    assert!(err.is_io());
+    assert!(err
+        .to_string()
+        .contains("maximum nesting depth of 2 exceeded"));
+}
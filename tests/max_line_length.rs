@@ -0,0 +1,83 @@
+// MIT License
+//
+// Copyright (c) 2024 Robin Doer
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+mod common;
+
+use json_pretty_compact::PrettyCompactFormatter;
+
+use crate::common::*;
+
+#[test]
+fn last_member_fills_line() {
+    let value = parse_json_string(r#"{ "abc": [1, 2, 3] }"#);
+    let json = serialize_to_string(
+        &value,
+        PrettyCompactFormatter::new().with_max_line_length(20),
+    );
+
+    assert_eq!(json, "{\n  \"abc\": [ 1, 2, 3 ]\n}");
+}
+
+#[test]
+fn trailing_comma_exceeds_line() {
+    let value = parse_json_string(r#"{ "abc": [1, 2, 3], "d": 1 }"#);
+    let json = serialize_to_string(
+        &value,
+        PrettyCompactFormatter::new().with_max_line_length(20),
+    );
+
+    assert_eq!(
+        json,
+        "{\n  \"abc\": [\n    1,\n    2,\n    3\n  ],\n  \"d\": 1\n}"
+    );
+}
+
+#[test]
+fn fixtures_within_limit() {
+    for name in ["array-03", "expense", "object-02", "readme"] {
+        let value = parse_json(name);
+
+        for max in 20..=120 {
+            let formatter = PrettyCompactFormatter::new().with_max_line_length(max);
+            let json = serialize_to_string(&value, formatter);
+
+            // A line can only exceed the limit, if it does not contain any
+            // compacted container.
+            for line in json.lines().filter(|l| l.chars().count() > max as usize) {
+                assert!(
+                    !line.ends_with(" ]") && !line.ends_with(" ],"),
+                    "{}@{}: {}",
+                    name,
+                    max,
+                    line
+                );
+                assert!(
+                    !line.ends_with(" }") && !line.ends_with(" },"),
+                    "{}@{}: {}",
+                    name,
+                    max,
+                    line
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,195 @@
+// MIT License
+//
+// Copyright (c) 2024 Robin Doer
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+mod common;
+
+use json_pretty_compact::PrettyCompactFormatter;
+
+use crate::common::*;
+
+const JSON: &str = r#"{
+  "header": { "title": "numbers", "tags": ["a", "b"] },
+  "data": [[1, 2, 3, 4, 5, 6, 7, 8, 9, 10], [11, 12, 13, 14, 15, 16, 17, 18, 19, 20]]
+}"#;
+
+#[test]
+fn indent_at_depth() {
+    let value = parse_json_string(JSON);
+    let formatter = PrettyCompactFormatter::new()
+        .with_max_line_length(40)
+        .with_indent_at_depth(0, 4);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(
+        json,
+        r#"{
+    "header": {
+      "title": "numbers",
+      "tags": [ "a", "b" ]
+    },
+    "data": [
+      [ 1, 2, 3, 4, 5, 6, 7, 8, 9, 10 ],
+      [
+        11,
+        12,
+        13,
+        14,
+        15,
+        16,
+        17,
+        18,
+        19,
+        20
+      ]
+    ]
+}"#
+    );
+}
+
+#[test]
+fn indent_at_path() {
+    let value = parse_json_string(JSON);
+    let formatter = PrettyCompactFormatter::no_rules()
+        .with_indent_at("/header", 4)
+        .with_indent_at("/header/tags", 1);
+    let json = serialize_to_string(&value, formatter);
+
+    assert!(json.starts_with(
+        r#"{
+  "header": {
+      "title": "numbers",
+      "tags": [
+       "a",
+       "b"
+      ]
+  },
+  "data": [
+    [
+      1,"#
+    ));
+}
+
+#[test]
+fn max_line_length_at_path() {
+    let value = parse_json_string(JSON);
+    let formatter = PrettyCompactFormatter::new()
+        .with_max_line_length(40)
+        .with_max_line_length_at("/data", 200);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(
+        json,
+        r#"{
+  "header": {
+    "title": "numbers",
+    "tags": [ "a", "b" ]
+  },
+  "data": [ [ 1, 2, 3, 4, 5, 6, 7, 8, 9, 10 ], [ 11, 12, 13, 14, 15, 16, 17, 18, 19, 20 ] ]
+}"#
+    );
+}
+
+#[test]
+fn max_line_length_at_depth() {
+    let value = parse_json_string(JSON);
+    let formatter = PrettyCompactFormatter::new()
+        .with_max_line_length(60)
+        .with_max_line_length_at_depth(2, 20);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(
+        json,
+        r#"{
+  "header": {
+    "title": "numbers",
+    "tags": [
+      "a",
+      "b"
+    ]
+  },
+  "data": [
+    [
+      1,
+      2,
+      3,
+      4,
+      5,
+      6,
+      7,
+      8,
+      9,
+      10
+    ],
+    [
+      11,
+      12,
+      13,
+      14,
+      15,
+      16,
+      17,
+      18,
+      19,
+      20
+    ]
+  ]
+}"#
+    );
+}
+
+#[test]
+fn max_line_length_nested_path() {
+    // the whole document fits into 200 columns, but not /data into 20
+    let value = parse_json_string(r#"{ "a": 1, "data": [1, 2, 3, 4, 5, 6, 7, 8] }"#);
+    let formatter = PrettyCompactFormatter::new()
+        .with_max_line_length(200)
+        .with_max_line_length_at("/data", 20);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(
+        json,
+        r#"{
+  "a": 1,
+  "data": [
+    1,
+    2,
+    3,
+    4,
+    5,
+    6,
+    7,
+    8
+  ]
+}"#
+    );
+}
+
+#[test]
+fn max_line_length_nested_depth() {
+    let value = parse_json_string(r#"{ "a": 1, "data": [1, 2] }"#);
+    let formatter = PrettyCompactFormatter::new()
+        .with_max_line_length(200)
+        .with_max_line_length_at_depth(1, 20);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(json, "{\n  \"a\": 1,\n  \"data\": [ 1, 2 ]\n}");
+}
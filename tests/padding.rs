@@ -0,0 +1,89 @@
+// MIT License
+//
+// Copyright (c) 2024 Robin Doer
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+mod common;
+
+use json_pretty_compact::{Layout, PrettyCompactFormatter};
+
+use crate::common::*;
+
+#[test]
+fn bracket() {
+    let value = parse_json_string(r#"{ "a": [1, 2], "b": { "c": 3 } }"#);
+    let formatter = PrettyCompactFormatter::new().with_bracket_padding(false);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(json, r#"{"a": [1, 2], "b": {"c": 3}}"#);
+}
+
+#[test]
+fn empty() {
+    let value = parse_json_string(r#"{ "a": [], "b": {} }"#);
+    let formatter = PrettyCompactFormatter::new().with_empty_padding(false);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(json, r#"{ "a": [], "b": {} }"#);
+}
+
+#[test]
+fn width() {
+    // { "a": [ 1, 2, 3 ] } needs 20 columns
+    let value = parse_json_string(r#"{ "a": [1, 2, 3] }"#);
+    let formatter = PrettyCompactFormatter::new()
+        .with_max_line_length(16)
+        .with_bracket_padding(false);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(json, r#"{"a": [1, 2, 3]}"#);
+}
+
+#[test]
+fn expanded_empty() {
+    let value = parse_json_string(r#"{ "a": [], "b": {} }"#);
+    let formatter = PrettyCompactFormatter::new()
+        .with_rule("/a", Layout::Expanded)
+        .with_min_compact_depth(2)
+        .with_empty_padding(false);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(json, "{\n  \"a\": [],\n  \"b\": {}\n}");
+}
+
+#[test]
+fn hugging_leading_commas() {
+    let value =
+        parse_json_string(r#"{ "a": { "k0xx": { "k0x": "s", "k1xx": "ssssss", "k2": 567.36 } } }"#);
+    let formatter = PrettyCompactFormatter::new()
+        .with_max_line_length(21)
+        .with_hugging(true)
+        .with_leading_commas(true)
+        .with_bracket_padding(false);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(
+        json,
+        r#"{ "a": { "k0xx": { "k0x": "s"
+                 , "k1xx": "ssssss"
+                 , "k2": 567.36
+                 } } }"#
+    );
+}
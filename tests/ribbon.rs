@@ -0,0 +1,137 @@
+// MIT License
+//
+// Copyright (c) 2024 Robin Doer
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+mod common;
+
+use json_pretty_compact::{Fill, PrettyCompactFormatter};
+
+use crate::common::*;
+
+#[test]
+fn ribbon() {
+    let value = parse_json_string(r#"{ "a": { "b": [1, 2, 3], "c": [4, 5] } }"#);
+    let formatter = PrettyCompactFormatter::new()
+        .with_max_line_length(40)
+        .with_ribbon_width(14);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(
+        json,
+        r#"{
+  "a": {
+    "b": [
+      1,
+      2,
+      3
+    ],
+    "c": [ 4, 5 ]
+  }
+}"#
+    );
+}
+
+#[test]
+fn indentation() {
+    // "b": [ 1, 2, 3 ], has 17 columns after the indentation
+    let value = parse_json_string(r#"{ "a": { "b": [1, 2, 3], "c": 4 } }"#);
+    let formatter = PrettyCompactFormatter::new()
+        .with_max_line_length(40)
+        .with_indent(8)
+        .with_ribbon_width(17);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(
+        json,
+        r#"{
+        "a": {
+                "b": [ 1, 2, 3 ],
+                "c": 4
+        }
+}"#
+    );
+}
+
+#[test]
+fn fill() {
+    let value = parse_json_string(r#"{ "a": [1, 2, 3, 4, 5, 6, 7, 8] }"#);
+    let formatter = PrettyCompactFormatter::new()
+        .with_max_line_length(40)
+        .with_ribbon_width(12)
+        .with_array_fill(Fill::All);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(
+        json,
+        r#"{
+  "a": [
+    1, 2, 3, 4,
+    5, 6, 7, 8
+  ]
+}"#
+    );
+}
+
+#[test]
+fn leading_commas() {
+    let value =
+        parse_json_string(r#"{ "a": 1, "k1xx": ["sss", false, "sssss", "x", "y", "zzzz", "q"] }"#);
+    let formatter = PrettyCompactFormatter::new()
+        .with_ribbon_width(16)
+        .with_leading_commas(true)
+        .with_array_fill(Fill::All);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(
+        json,
+        r#"{ "a": 1
+, "k1xx": [ "sss"
+          , false, "sssss"
+          , "x", "y"
+          , "zzzz", "q"
+          ]
+}"#
+    );
+}
+
+#[test]
+fn leading_commas_nested() {
+    let value = parse_json_string(r#"["ssss", [29.13, [112.21, false, true], 249.34]]"#);
+    let formatter = PrettyCompactFormatter::new()
+        .with_ribbon_width(13)
+        .with_leading_commas(true)
+        .with_array_fill(Fill::All)
+        .with_compact_separators(",", ":")
+        .with_expanded_separators(",", ":");
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(
+        json,
+        r#"["ssss"
+,[29.13
+ ,[112.21
+  ,false,true
+  ]
+ ,249.34
+ ]
+]"#
+    );
+}
@@ -0,0 +1,150 @@
+// MIT License
+//
+// Copyright (c) 2024 Robin Doer
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+mod common;
+
+use json_pretty_compact::PrettyCompactFormatter;
+
+use crate::common::*;
+
+#[test]
+fn compact() {
+    let value = parse_json_string(r#"{ "a": [1, 2], "b": { "c": 3 } }"#);
+    let formatter = PrettyCompactFormatter::new()
+        .with_bracket_padding(false)
+        .with_compact_separators(",", ":");
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(json, r#"{"a":[1,2],"b":{"c":3}}"#);
+}
+
+#[test]
+fn expanded() {
+    let value = parse_json_string(r#"{ "a": [1, 2], "b": 3 }"#);
+    let formatter = PrettyCompactFormatter::new()
+        .with_max_line_length(18)
+        .with_expanded_separators(" , ", " : ");
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(
+        json,
+        r#"{
+  "a" : [ 1, 2 ] ,
+  "b" : 3
+}"#
+    );
+}
+
+#[test]
+fn width() {
+    // [ 1, 2, 3, 4 ] needs 14 columns
+    let value = parse_json_string("[1, 2, 3, 4]");
+    let formatter = PrettyCompactFormatter::new()
+        .with_max_line_length(13)
+        .with_compact_separators(",", ":");
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(json, "[ 1,2,3,4 ]");
+}
+
+#[test]
+fn suffix() {
+    // "a" : [ 1, 2 ] fits, but not the trailing " ,"
+    let value = parse_json_string(r#"{ "a": [1, 2], "b": 3 }"#);
+    let formatter = PrettyCompactFormatter::new()
+        .with_max_line_length(17)
+        .with_expanded_separators(" , ", " : ");
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(
+        json,
+        r#"{
+  "a" : [
+    1 ,
+    2
+  ] ,
+  "b" : 3
+}"#
+    );
+}
+
+#[test]
+fn leading_commas() {
+    let value = parse_json_string(r#"[1, [2, 3], { "a": [4, 5] }]"#);
+    let formatter = PrettyCompactFormatter::new()
+        .with_max_line_length(10)
+        .with_leading_commas(true)
+        .with_bracket_padding(false)
+        .with_expanded_separators(",", ":");
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(
+        json,
+        r#"[1
+,[2, 3]
+,{"a":[4
+      ,5
+      ]
+ }
+]"#
+    );
+}
+
+#[test]
+fn grid() {
+    let value = parse_json_string("[1, 22, 333, 4, 5, 6]");
+    let formatter = PrettyCompactFormatter::new()
+        .with_max_line_length(12)
+        .with_array_grid(true)
+        .with_expanded_separators(",", ":");
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(json, "[\n  1,22,333,\n  4,5, 6\n]");
+}
+
+#[test]
+fn hugging_leading_commas() {
+    let value = parse_json_string("[[1111, 2222, 3333, 4444]]");
+    let formatter = PrettyCompactFormatter::new()
+        .with_max_line_length(10)
+        .with_hugging(true)
+        .with_leading_commas(true)
+        .with_expanded_separators(",", ":");
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(json, "[[1111\n ,2222\n ,3333\n ,4444\n ]]");
+}
+
+#[test]
+fn grid_lisp_closing() {
+    // 370.14 , false , exceeds the line, although the closing bracket fits
+    let value = parse_json_string("[370.14, false, true]");
+    let formatter = PrettyCompactFormatter::new()
+        .with_max_line_length(17)
+        .with_array_grid(true)
+        .with_lisp_closing(true)
+        .with_bracket_padding(false)
+        .with_expanded_separators(" , ", " : ");
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(json, "[\n  370.14 ,\n  false ,\n  true]");
+}
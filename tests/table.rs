@@ -0,0 +1,133 @@
+// MIT License
+//
+// Copyright (c) 2024 Robin Doer
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+mod common;
+
+use json_pretty_compact::{Layout, PrettyCompactFormatter};
+
+use crate::common::*;
+
+const ROWS: &str = r#"[
+  { "name": "Joe", "age": 7, "active": true },
+  { "name": "Beth", "age": 42, "active": false },
+  { "name": "Jo", "age": 100, "active": null }
+]"#;
+
+#[test]
+fn table() {
+    let value = parse_json_string(ROWS);
+    let formatter = PrettyCompactFormatter::new()
+        .with_max_line_length(60)
+        .with_table(true);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(
+        json,
+        r#"[
+  { "name": "Joe",  "age": 7,   "active": true  },
+  { "name": "Beth", "age": 42,  "active": false },
+  { "name": "Jo",   "age": 100, "active": null  }
+]"#
+    );
+}
+
+#[test]
+fn too_wide() {
+    let value = parse_json_string(ROWS);
+    let formatter = PrettyCompactFormatter::new()
+        .with_max_line_length(49)
+        .with_table(true);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(
+        json,
+        r#"[
+  { "name": "Joe", "age": 7, "active": true },
+  { "name": "Beth", "age": 42, "active": false },
+  { "name": "Jo", "age": 100, "active": null }
+]"#
+    );
+}
+
+#[test]
+fn different_keys() {
+    let value = parse_json_string(r#"[{ "a": 1, "b": 22 }, { "b": 3, "a": 4 }]"#);
+    let formatter = PrettyCompactFormatter::new()
+        .with_max_line_length(22)
+        .with_table(true);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(
+        json,
+        r#"[
+  { "a": 1, "b": 22 },
+  { "b": 3, "a": 4 }
+]"#
+    );
+}
+
+#[test]
+fn max_inline_items() {
+    let value = parse_json_string(r#"[{"a":1,"b":2,"c":3},{"a":10,"b":20,"c":30}]"#);
+    let formatter = PrettyCompactFormatter::new()
+        .with_max_line_length(30)
+        .with_table(true)
+        .with_max_inline_items(2, 2);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(
+        json,
+        r#"[
+  {
+    "a": 1,
+    "b": 2,
+    "c": 3
+  },
+  {
+    "a": 10,
+    "b": 20,
+    "c": 30
+  }
+]"#
+    );
+}
+
+#[test]
+fn rule() {
+    let value = parse_json_string(r#"[{"a":1,"b":2},{"a":10,"b":20}]"#);
+    let formatter = PrettyCompactFormatter::new()
+        .with_max_line_length(30)
+        .with_table(true)
+        .with_rule("/1", Layout::Expanded);
+    let json = serialize_to_string(&value, formatter);
+
+    assert_eq!(
+        json,
+        r#"[
+  { "a": 1, "b": 2 },
+  {
+    "a": 10,
+    "b": 20
+  }
+]"#
+    );
+}